@@ -0,0 +1,123 @@
+use std::{
+    fs::{self, File},
+    hint::black_box,
+    io::{self, Read},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rxd::{Dumper, PlainRenderer};
+
+/// Size of the buffer being dumped
+const LENGTH: usize = 100 << 20;
+
+fn dump(c: &mut Criterion) {
+    let bytes = (0..LENGTH as u32)
+        .map(|i| (i.wrapping_mul(0x9e3779b1) >> 24) as u8)
+        .collect::<Vec<_>>();
+    let mut output = Vec::with_capacity(5 * LENGTH);
+
+    let mut group = c.benchmark_group("dump");
+    group.throughput(Throughput::Bytes(LENGTH as u64));
+    group.sample_size(10);
+
+    group.bench_function("classic", |b| {
+        b.iter(|| {
+            output.clear();
+            Dumper::from_bytes(black_box(&bytes))
+                .dump_to(&mut output)
+                .unwrap();
+        })
+    });
+
+    group.bench_function("plain", |b| {
+        b.iter(|| {
+            output.clear();
+            Dumper::from_bytes(black_box(&bytes))
+                .with_renderer(Box::new(PlainRenderer::new()))
+                .dump_to(&mut output)
+                .unwrap();
+        })
+    });
+
+    group.finish();
+}
+
+/// A reader passing on at most one line of bytes per call, like reading the input a line at a time
+struct PerLine<R>(R);
+
+impl<R: Read> Read for PerLine<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = buf.len().min(16);
+        self.0.read(&mut buf[..length])
+    }
+}
+
+fn read(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("rxd-bench-read.bin");
+    let bytes = (0..LENGTH as u32)
+        .map(|i| (i.wrapping_mul(0x9e3779b1) >> 24) as u8)
+        .collect::<Vec<_>>();
+    fs::write(&path, &bytes).unwrap();
+    drop(bytes);
+    let mut output = Vec::with_capacity(5 * LENGTH);
+
+    let mut group = c.benchmark_group("read");
+    group.throughput(Throughput::Bytes(LENGTH as u64));
+    group.sample_size(10);
+
+    group.bench_function("buffered", |b| {
+        b.iter(|| {
+            output.clear();
+            Dumper::new(File::open(&path).unwrap())
+                .dump_to(&mut output)
+                .unwrap();
+        })
+    });
+
+    group.bench_function("per_line", |b| {
+        b.iter(|| {
+            output.clear();
+            Dumper::new(PerLine(File::open(&path).unwrap()))
+                .dump_to(&mut output)
+                .unwrap();
+        })
+    });
+
+    group.finish();
+    fs::remove_file(&path).unwrap();
+}
+
+/// Size of the buffer being dumped to compare formatting on one and several threads
+#[cfg(feature = "rayon")]
+const PARALLEL_LENGTH: usize = 500 << 20;
+
+#[cfg(feature = "rayon")]
+fn parallel(c: &mut Criterion) {
+    let bytes = (0..PARALLEL_LENGTH as u32)
+        .map(|i| (i.wrapping_mul(0x9e3779b1) >> 24) as u8)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("parallel");
+    group.throughput(Throughput::Bytes(PARALLEL_LENGTH as u64));
+    group.sample_size(10);
+
+    group.bench_function("render", |b| {
+        b.iter(|| Dumper::from_bytes(black_box(&bytes)).render().unwrap())
+    });
+
+    group.bench_function("render_parallel", |b| {
+        b.iter(|| {
+            Dumper::from_bytes(black_box(&bytes))
+                .render_parallel()
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+criterion_group!(benches, dump, read);
+#[cfg(feature = "rayon")]
+criterion_group!(benches, dump, read, parallel);
+criterion_main!(benches);
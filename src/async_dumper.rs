@@ -0,0 +1,138 @@
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{ColorChoice, Dumper};
+
+/// A hex dumper for asynchronous readers, formatting lines the same way as [`Dumper`]
+pub struct AsyncDumper<R>
+where
+    R: AsyncRead + Unpin,
+{
+    reader: R,
+    /// Holds the layout options, which are applied by turning it into a [`WritingDumper`](crate::WritingDumper)
+    options: Dumper<io::Empty>,
+    skip: u64,
+    byte_count: Option<u64>,
+    base_address: u64,
+}
+
+impl<R> AsyncDumper<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Construct a new instance of [`AsyncDumper`]
+    pub fn new(reader: R) -> AsyncDumper<R> {
+        AsyncDumper {
+            reader,
+            options: Dumper::new(io::empty()),
+            skip: 0,
+            byte_count: None,
+            base_address: 0,
+        }
+    }
+
+    /// Set the number of bytes per line to be printed, see [`Dumper::line_width`]
+    pub fn line_width(mut self, line_width: usize) -> AsyncDumper<R> {
+        self.options = self.options.line_width(line_width);
+        self
+    }
+
+    /// Set the number of bytes grouped together per line to be printed, see [`Dumper::byte_group_length`]
+    pub fn byte_group_length(mut self, byte_group_length: usize) -> AsyncDumper<R> {
+        self.options = self.options.byte_group_length(byte_group_length);
+        self
+    }
+
+    /// Set the number of bytes at the start of the input to read and discard before dumping
+    pub fn skip(mut self, bytes: u64) -> AsyncDumper<R> {
+        self.skip = bytes;
+        self
+    }
+
+    /// Set the maximum number of bytes to be dumped
+    pub fn byte_count(mut self, byte_count: Option<u64>) -> AsyncDumper<R> {
+        self.byte_count = byte_count;
+        self
+    }
+
+    /// Set the address added to every offset printed, see [`Dumper::base_address`]
+    pub fn base_address(mut self, base_address: u64) -> AsyncDumper<R> {
+        self.base_address = base_address;
+        self
+    }
+
+    /// Set when bytes should be colored by category, see [`Dumper::color_choice`]
+    ///
+    /// [`ColorChoice::Auto`] never colors the output, since the writer isn't known to be a terminal.
+    pub fn color_choice(mut self, color_choice: ColorChoice) -> AsyncDumper<R> {
+        self.options = self.options.color_choice(color_choice);
+        self
+    }
+
+    /// Read the whole input and write the formatted dump to `writer`
+    pub async fn dump_to<W: AsyncWrite + Unpin>(mut self, writer: &mut W) -> io::Result<()> {
+        if self.skip > 0 {
+            tokio::io::copy(
+                &mut (&mut self.reader).take(self.skip),
+                &mut tokio::io::sink(),
+            )
+            .await?;
+        }
+
+        let mut dumper = self
+            .options
+            .base_address(self.base_address + self.skip)
+            .into_writer(Vec::new());
+
+        let mut reader = (&mut self.reader).take(self.byte_count.unwrap_or(u64::MAX));
+        let mut buf = vec![0; 0x1000];
+        loop {
+            let length = reader.read(&mut buf).await?;
+            if length == 0 {
+                break;
+            }
+
+            // formatting is synchronous, so the complete lines are collected in memory and then written out
+            io::Write::write_all(&mut dumper, &buf[..length])?;
+            let output = dumper.output_mut();
+            writer.write_all(output).await?;
+            output.clear();
+        }
+
+        let output = dumper.finish()?;
+        writer.write_all(&output).await?;
+        writer.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn matches_dumper() {
+        let bytes = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+
+        let expected = Dumper::new(&bytes[..])
+            .line_width(8)
+            .byte_group_length(2)
+            .skip(3)
+            .byte_count(Some(40))
+            .render()
+            .unwrap()
+            + "\n";
+
+        let mut output = Vec::new();
+        AsyncDumper::new(&bytes[..])
+            .line_width(8)
+            .byte_group_length(2)
+            .skip(3)
+            .byte_count(Some(40))
+            .dump_to(&mut output)
+            .await
+            .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::env;
+
+/// When to color the output of a [`Dumper`](crate::Dumper)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Always color the output
+    Always,
+    /// Never color the output
+    Never,
+    /// Color the output only if it is written to a terminal and the [`NO_COLOR`] environment variable is unset
+    ///
+    /// [`NO_COLOR`]: https://no-color.org/
+    Auto,
+}
+
+impl ColorChoice {
+    /// Whether output should be colored, given whether it is written to a terminal
+    #[cfg(feature = "std")]
+    pub fn enabled(self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                is_terminal && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+            }
+        }
+    }
+}
+
+/// A terminal color, printed with ANSI escape sequences
+///
+/// The standard and bright colors are taken from the palette of the terminal, so they look different depending on
+/// its theme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// A color of the 256-color palette
+    Fixed(u8),
+    /// A 24-bit color, which not every terminal supports
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The [SGR] parameters setting this color, as the background color if `background` is set
+    ///
+    /// [SGR]: https://en.wikipedia.org/wiki/ANSI_escape_code#SGR_(Select_Graphic_Rendition)_parameters
+    fn sgr_code(self, background: bool) -> String {
+        let (base, bright_base, extended) = match background {
+            true => (40, 100, 48),
+            false => (30, 90, 38),
+        };
+        let code = match self {
+            Color::Black => base,
+            Color::Red => base + 1,
+            Color::Green => base + 2,
+            Color::Yellow => base + 3,
+            Color::Blue => base + 4,
+            Color::Magenta => base + 5,
+            Color::Cyan => base + 6,
+            Color::White => base + 7,
+            Color::BrightBlack => bright_base,
+            Color::BrightRed => bright_base + 1,
+            Color::BrightGreen => bright_base + 2,
+            Color::BrightYellow => bright_base + 3,
+            Color::BrightBlue => bright_base + 4,
+            Color::BrightMagenta => bright_base + 5,
+            Color::BrightCyan => bright_base + 6,
+            Color::BrightWhite => bright_base + 7,
+            Color::Fixed(index) => return format!("{extended};5;{index}"),
+            Color::Rgb(red, green, blue) => return format!("{extended};2;{red};{green};{blue}"),
+        };
+        format!("{code}")
+    }
+}
+
+/// The colors a byte is printed in when the output is colored, see
+/// [`Dumper::style_fn`](crate::Dumper::style_fn)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ByteStyle {
+    foreground: Option<Color>,
+    background: Option<Color>,
+}
+
+impl ByteStyle {
+    /// Construct a new instance of [`ByteStyle`] that leaves the byte uncolored
+    pub fn new() -> ByteStyle {
+        ByteStyle::default()
+    }
+
+    /// The style used when no style function is set, which colors `byte` by its category: gray for null bytes, cyan
+    /// for printable ASCII characters, green for ASCII control codes and yellow for non-ASCII bytes
+    pub fn of_category(byte: u8) -> ByteStyle {
+        let color = match ByteCategory::of(byte) {
+            ByteCategory::Null => Color::BrightBlack,
+            ByteCategory::Printable => Color::Cyan,
+            ByteCategory::Control => Color::Green,
+            ByteCategory::High => Color::Yellow,
+        };
+        ByteStyle::new().foreground(color)
+    }
+
+    /// Set the color of the text
+    pub fn foreground(mut self, color: Color) -> ByteStyle {
+        self.foreground = Some(color);
+        self
+    }
+
+    /// Set the color behind the text
+    pub fn background(mut self, color: Color) -> ByteStyle {
+        self.background = Some(color);
+        self
+    }
+
+    /// Wrap `text` in the ANSI escape sequences for this style, leaving it as is if no color is set
+    pub(crate) fn paint(self, text: &str) -> String {
+        let codes: Vec<String> = [(self.foreground, false), (self.background, true)]
+            .into_iter()
+            .filter_map(|(color, background)| Some(color?.sgr_code(background)))
+            .collect();
+        match codes.is_empty() {
+            true => String::from(text),
+            false => format!("\x1b[{}m{text}\x1b[0m", codes.join(";")),
+        }
+    }
+}
+
+/// Broad category of a byte value, used to decide how it is highlighted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ByteCategory {
+    /// `0x00`
+    Null,
+    /// Printable ASCII characters `0x20..=0x7e`
+    Printable,
+    /// ASCII control codes `0x01..=0x1f` and `0x7f`
+    Control,
+    /// Non-ASCII bytes `0x80..=0xff`
+    High,
+}
+
+impl ByteCategory {
+    pub(crate) fn of(byte: u8) -> ByteCategory {
+        match byte {
+            0x00 => ByteCategory::Null,
+            0x20..=0x7e => ByteCategory::Printable,
+            0x01..=0x1f | 0x7f => ByteCategory::Control,
+            0x80..=0xff => ByteCategory::High,
+        }
+    }
+}
+
+impl ByteCategory {
+    /// The CSS class of the HTML elements wrapping bytes of this category
+    fn class_name(self) -> &'static str {
+        match self {
+            ByteCategory::Null => "rxd-null",
+            ByteCategory::Printable => "rxd-printable",
+            ByteCategory::Control => "rxd-control",
+            ByteCategory::High => "rxd-high",
+        }
+    }
+}
+
+/// Escape the characters of `text` that have a meaning in HTML
+pub(crate) fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escape `text` and wrap it in a `<span>` with the CSS class of `byte`'s category
+pub(crate) fn html_span(byte: u8, text: &str) -> String {
+    let text = html_escape(text);
+    format!(
+        "<span class=\"{}\">{text}</span>",
+        ByteCategory::of(byte).class_name()
+    )
+}
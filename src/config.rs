@@ -0,0 +1,488 @@
+use std::{
+    io::{self, Read, Seek},
+    ops::Range,
+};
+
+use crate::{
+    ByteStyle, ChecksumKind, ColorChoice, Dumper, DumperError, Endian, FloatWidth, IntWidth,
+    LineTerminator, OffsetBase, Radix, SeparatorStyle, TextEncoding, TextMode,
+};
+
+/// The options of a [`Dumper`] without its reader, so that several inputs can be dumped with the same options
+///
+/// It has the same setters as [`Dumper`], and [`DumperConfig::build`] makes a dumper with these options for each
+/// reader. Custom renderers keep state from one line of a dump to the next and can't be shared, so they are set on
+/// each dumper with [`Dumper::with_renderer`] instead.
+pub struct DumperConfig {
+    /// Holds the options, which are copied into every dumper built
+    options: Dumper<io::Empty>,
+}
+
+impl Default for DumperConfig {
+    fn default() -> Self {
+        DumperConfig {
+            options: Dumper::new(io::empty()),
+        }
+    }
+}
+
+impl Clone for DumperConfig {
+    fn clone(&self) -> Self {
+        DumperConfig {
+            options: self.options.copy_options(io::empty()),
+        }
+    }
+}
+
+impl Dumper<io::Empty> {
+    /// Construct a new [`DumperConfig`] with the default options, to be applied to readers later
+    pub fn builder() -> DumperConfig {
+        DumperConfig::new()
+    }
+}
+
+impl DumperConfig {
+    /// Construct a new instance of [`DumperConfig`] with the default options
+    pub fn new() -> DumperConfig {
+        DumperConfig::default()
+    }
+
+    /// Construct a new [`Dumper`] for `reader` with these options, like [`Dumper::new`]
+    pub fn build<R: Read>(&self, reader: R) -> Dumper<R> {
+        self.options.copy_options(reader)
+    }
+
+    /// Construct a new [`Dumper`] for a seekable `reader` with these options, like [`Dumper::from_seekable`]
+    pub fn build_seekable<R: Read + Seek>(&self, reader: R) -> Dumper<R> {
+        self.options.copy_options(reader).into_seekable()
+    }
+
+    /// Print the formatted dump of `reader` with these options, see [`Dumper::dump`]
+    pub fn dump<R: Read>(&self, reader: R) -> io::Result<()> {
+        self.build(reader).dump()
+    }
+
+    /// Set whether C0 control codes should be displayed as Control Pictures characters, see
+    /// [`Dumper::control_pictures`]
+    pub fn control_pictures(mut self, control_pictures: bool) -> DumperConfig {
+        self.options = self.options.control_pictures(control_pictures);
+        self
+    }
+
+    /// Set the character set used to decode the bytes shown in the text column, see [`Dumper::text_encoding`]
+    pub fn text_encoding(mut self, text_encoding: TextEncoding) -> DumperConfig {
+        self.options = self.options.text_encoding(text_encoding);
+        self
+    }
+
+    /// Set a function choosing the colors of each byte, see [`Dumper::style_fn`]
+    pub fn style_fn(
+        mut self,
+        style_fn: impl Fn(u64, u8) -> ByteStyle + Send + Sync + 'static,
+    ) -> DumperConfig {
+        self.options = self.options.style_fn(style_fn);
+        self
+    }
+
+    /// Set a function returning the character shown for each byte in the text column, see [`Dumper::text_renderer`]
+    pub fn text_renderer(
+        mut self,
+        text_renderer: impl Fn(u8) -> char + Send + Sync + 'static,
+    ) -> DumperConfig {
+        self.options = self.options.text_renderer(text_renderer);
+        self
+    }
+
+    /// Set whether a column with the entropy of each line should be printed, see [`Dumper::entropy`]
+    pub fn entropy(mut self, entropy: bool) -> DumperConfig {
+        self.options = self.options.entropy(entropy);
+        self
+    }
+
+    /// Set the checksum of each line printed in a column of its own, see [`Dumper::line_checksum`]
+    pub fn line_checksum(mut self, kind: Option<ChecksumKind>) -> DumperConfig {
+        self.options = self.options.line_checksum(kind);
+        self
+    }
+
+    /// Set the character shown in the text column for bytes that can't be printed, see [`Dumper::placeholder`]
+    pub fn placeholder(mut self, placeholder: char) -> DumperConfig {
+        self.options = self.options.placeholder(placeholder);
+        self
+    }
+
+    /// Set the character shown in the text column for bytes that can't be printed, returning an error if it isn't a
+    /// single column wide, see [`Dumper::try_placeholder`]
+    pub fn try_placeholder(self, placeholder: char) -> Result<DumperConfig, DumperError> {
+        let options = self.options.try_placeholder(placeholder)?;
+        Ok(DumperConfig { options })
+    }
+
+    /// Set how bytes are represented in the text column, see [`Dumper::text_mode`]
+    pub fn text_mode(mut self, text_mode: TextMode) -> DumperConfig {
+        self.options = self.options.text_mode(text_mode);
+        self
+    }
+
+    /// Set whether hex digits in the offset and byte columns should be printed in uppercase, see
+    /// [`Dumper::uppercase`]
+    pub fn uppercase(mut self, uppercase: bool) -> DumperConfig {
+        self.options = self.options.uppercase(uppercase);
+        self
+    }
+
+    /// Set whether bytes should be colored by category, see [`Dumper::color`]
+    pub fn color(mut self, color: bool) -> DumperConfig {
+        self.options = self.options.color(color);
+        self
+    }
+
+    /// Set whether the dump should be formatted as HTML, see [`Dumper::html`]
+    pub fn html(mut self, html: bool) -> DumperConfig {
+        self.options = self.options.html(html);
+        self
+    }
+
+    /// Set when bytes should be colored by category, see [`Dumper::color_choice`]
+    pub fn color_choice(mut self, color_choice: ColorChoice) -> DumperConfig {
+        self.options = self.options.color_choice(color_choice);
+        self
+    }
+
+    /// Set the numeral system used to print the offset column, see [`Dumper::offset_base`]
+    pub fn offset_base(mut self, offset_base: OffsetBase) -> DumperConfig {
+        self.options = self.options.offset_base(offset_base);
+        self
+    }
+
+    /// Set the number of digits of every offset, see [`Dumper::offset_width`]
+    pub fn offset_width(mut self, digits: Option<usize>) -> DumperConfig {
+        self.options = self.options.offset_width(digits);
+        self
+    }
+
+    /// Set whether each offset should be followed by its decimal value in parentheses, see [`Dumper::offset_both`]
+    pub fn offset_both(mut self, enabled: bool) -> DumperConfig {
+        self.options = self.options.offset_both(enabled);
+        self
+    }
+
+    /// Set a function formatting the offset column, see [`Dumper::offset_formatter`]
+    pub fn offset_formatter(
+        mut self,
+        offset_formatter: impl Fn(u64) -> String + Send + Sync + 'static,
+    ) -> DumperConfig {
+        self.options = self.options.offset_formatter(offset_formatter);
+        self
+    }
+
+    /// Set the address shown in the offset column for the first byte of the input, see [`Dumper::base_address`]
+    pub fn base_address(mut self, base_address: u64) -> DumperConfig {
+        self.options = self.options.base_address(base_address);
+        self
+    }
+
+    /// Set the number of lines to print, see [`Dumper::line_count`]
+    pub fn line_count(mut self, line_count: Option<usize>) -> DumperConfig {
+        self.options = self.options.line_count(line_count);
+        self
+    }
+
+    /// Set the range of offsets `start..end` of the input to print, see [`Dumper::range`]
+    pub fn range(mut self, start: u64, end: u64) -> DumperConfig {
+        self.options = self.options.range(start, end);
+        self
+    }
+
+    /// Set the range of offsets `start..end` of the input to print, returning an error if `end` is before `start`,
+    /// see [`Dumper::try_range`]
+    pub fn try_range(self, start: u64, end: u64) -> Result<DumperConfig, DumperError> {
+        let options = self.options.try_range(start, end)?;
+        Ok(DumperConfig { options })
+    }
+
+    /// Set the range of offsets of the input to print to the annotated region labelled `name`, see
+    /// [`Dumper::region`]
+    pub fn region(mut self, name: &str) -> DumperConfig {
+        self.options = self.options.region(name);
+        self
+    }
+
+    /// Set the range of offsets of the input to print to the annotated region labelled `name`, returning an error if
+    /// there is no such region, see [`Dumper::try_region`]
+    pub fn try_region(self, name: &str) -> Result<DumperConfig, DumperError> {
+        let options = self.options.try_region(name)?;
+        Ok(DumperConfig { options })
+    }
+
+    /// Set whether the printed offsets count from the first byte dumped, see [`Dumper::relative_offsets`]
+    pub fn relative_offsets(mut self, relative_offsets: bool) -> DumperConfig {
+        self.options = self.options.relative_offsets(relative_offsets);
+        self
+    }
+
+    /// Set the total number of bytes to print, see [`Dumper::byte_count`]
+    pub fn byte_count(mut self, byte_count: Option<u64>) -> DumperConfig {
+        self.options = self.options.byte_count(byte_count);
+        self
+    }
+
+    /// Set the number of bytes at the start of the input to skip before dumping, see [`Dumper::skip`]
+    pub fn skip(mut self, bytes: u64) -> DumperConfig {
+        self.options = self.options.skip(bytes);
+        self
+    }
+
+    /// Set whether runs of identical lines should be collapsed into a single `*` line, see [`Dumper::squeeze`]
+    pub fn squeeze(mut self, squeeze: bool) -> DumperConfig {
+        self.options = self.options.squeeze(squeeze);
+        self
+    }
+
+    /// Set the number of lines after which a blank line is printed, see [`Dumper::block_spacing`]
+    pub fn block_spacing(mut self, every: Option<usize>) -> DumperConfig {
+        self.options = self.options.block_spacing(every);
+        self
+    }
+
+    /// Set whether the `*` line of each squeezed run should also say how many bytes were left out, see
+    /// [`Dumper::squeeze_count`]
+    pub fn squeeze_count(mut self, squeeze_count: bool) -> DumperConfig {
+        self.options = self.options.squeeze_count(squeeze_count);
+        self
+    }
+
+    /// Set a byte sequence that lines must contain in order to be printed, see [`Dumper::grep`]
+    pub fn grep(mut self, needle: Vec<u8>) -> DumperConfig {
+        self.options = self.options.grep(needle);
+        self
+    }
+
+    /// Set a byte sequence whose occurrences should be highlighted, see [`Dumper::highlight`]
+    pub fn highlight(mut self, pattern: Vec<u8>) -> DumperConfig {
+        self.options = self.options.highlight(pattern);
+        self
+    }
+
+    /// Set labels for ranges of offsets, appended as a comment to every line with bytes in the range, see
+    /// [`Dumper::annotate`]
+    pub fn annotate(mut self, ranges: Vec<(Range<u64>, String)>) -> DumperConfig {
+        self.options = self.options.annotate(ranges);
+        self
+    }
+
+    /// Set whether a line with the number of bytes dumped and their checksum should be printed after the dump, see
+    /// [`Dumper::summary`]
+    pub fn summary(mut self, summary: bool) -> DumperConfig {
+        self.options = self.options.summary(summary);
+        self
+    }
+
+    /// Set whether a histogram of the most common byte values should be printed after the dump, see
+    /// [`Dumper::histogram`]
+    pub fn histogram(mut self, histogram: bool) -> DumperConfig {
+        self.options = self.options.histogram(histogram);
+        self
+    }
+
+    /// Set a fill byte whose runs of at least `min_run` bytes are left out of the dump, see [`Dumper::collapse_fill`]
+    pub fn collapse_fill(mut self, byte: Option<u8>, min_run: usize) -> DumperConfig {
+        self.options = self.options.collapse_fill(byte, min_run);
+        self
+    }
+
+    /// Set how many lines of the input each printed line stands for when sampling, see [`Dumper::stride`]
+    pub fn stride(mut self, every: usize) -> DumperConfig {
+        self.options = self.options.stride(every);
+        self
+    }
+
+    /// Set the number of bytes per line to be printed, see [`Dumper::line_width`]
+    pub fn line_width(mut self, line_width: usize) -> DumperConfig {
+        self.options = self.options.line_width(line_width);
+        self
+    }
+
+    /// Set the number of bytes per line to be printed, returning an error if it is outside the range 1-256, see
+    /// [`Dumper::try_line_width`]
+    pub fn try_line_width(self, line_width: usize) -> Result<DumperConfig, DumperError> {
+        let options = self.options.try_line_width(line_width)?;
+        Ok(DumperConfig { options })
+    }
+
+    /// Set whether the line width should be chosen to fit the terminal, see [`Dumper::line_width_auto`]
+    pub fn line_width_auto(mut self, line_width_auto: bool) -> DumperConfig {
+        self.options = self.options.line_width_auto(line_width_auto);
+        self
+    }
+
+    /// Set whether the offset column should be printed, see [`Dumper::show_offset`]
+    pub fn show_offset(mut self, show_offset: bool) -> DumperConfig {
+        self.options = self.options.show_offset(show_offset);
+        self
+    }
+
+    /// Set whether the text column should be printed, see [`Dumper::show_ascii`]
+    pub fn show_ascii(mut self, show_ascii: bool) -> DumperConfig {
+        self.options = self.options.show_ascii(show_ascii);
+        self
+    }
+
+    /// Set the maximum number of bytes of each line shown in the text column, see [`Dumper::ascii_width`]
+    pub fn ascii_width(mut self, ascii_width: Option<usize>) -> DumperConfig {
+        self.options = self.options.ascii_width(ascii_width);
+        self
+    }
+
+    /// Set the text shown in the data column in place of each byte missing from the last line, see
+    /// [`Dumper::short_line_filler`]
+    pub fn short_line_filler(mut self, filler: Option<&str>) -> DumperConfig {
+        self.options = self.options.short_line_filler(filler);
+        self
+    }
+
+    /// Set the separators placed between columns, see [`Dumper::separators`]
+    pub fn separators(mut self, separators: SeparatorStyle) -> DumperConfig {
+        self.options = self.options.separators(separators);
+        self
+    }
+
+    /// Set whether the row of byte offsets and the separator row should be printed before the first line, see
+    /// [`Dumper::header`]
+    pub fn header(mut self, show: bool) -> DumperConfig {
+        self.options = self.options.header(show);
+        self
+    }
+
+    /// Set whether the dump should be printed with as little whitespace as possible, see [`Dumper::compact`]
+    pub fn compact(mut self, compact: bool) -> DumperConfig {
+        self.options = self.options.compact(compact);
+        self
+    }
+
+    /// Set whether each line should start with its line number within the dump, see [`Dumper::line_numbers`]
+    pub fn line_numbers(mut self, line_numbers: bool) -> DumperConfig {
+        self.options = self.options.line_numbers(line_numbers);
+        self
+    }
+
+    /// Set the characters ending each line, see [`Dumper::line_terminator`]
+    pub fn line_terminator(mut self, line_terminator: LineTerminator) -> DumperConfig {
+        self.options = self.options.line_terminator(line_terminator);
+        self
+    }
+
+    /// Set whether the last line should be ended with the line terminator too, see [`Dumper::trailing_newline`]
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> DumperConfig {
+        self.options = self.options.trailing_newline(trailing_newline);
+        self
+    }
+
+    /// Set the numeral system used to print each byte in the data column, see [`Dumper::radix`]
+    pub fn radix(mut self, radix: Radix) -> DumperConfig {
+        self.options = self.options.radix(radix);
+        self
+    }
+
+    /// Set the number of bytes after which an extra space is inserted between groups, see [`Dumper::group_gap`]
+    pub fn group_gap(mut self, every: Option<usize>) -> DumperConfig {
+        self.options = self.options.group_gap(every);
+        self
+    }
+
+    /// Set the byte order used to display each group of bytes, see [`Dumper::endian`]
+    pub fn endian(mut self, endian: Endian) -> DumperConfig {
+        self.options = self.options.endian(endian);
+        self
+    }
+
+    /// Set whether the bytes of each line should be shown in reverse order, see [`Dumper::reverse_line`]
+    pub fn reverse_line(mut self, enabled: bool) -> DumperConfig {
+        self.options = self.options.reverse_line(enabled);
+        self
+    }
+
+    /// Set the number of bytes grouped together per line to be printed, see [`Dumper::byte_group_length`]
+    pub fn byte_group_length(mut self, byte_group_length: usize) -> DumperConfig {
+        self.options = self.options.byte_group_length(byte_group_length);
+        self
+    }
+
+    /// Set the number of bytes grouped together per line to be printed, returning an error if it is outside the
+    /// range 1-256, see [`Dumper::try_byte_group_length`]
+    pub fn try_byte_group_length(
+        self,
+        byte_group_length: usize,
+    ) -> Result<DumperConfig, DumperError> {
+        let options = self.options.try_byte_group_length(byte_group_length)?;
+        Ok(DumperConfig { options })
+    }
+
+    /// Set whether each group of bytes should also be decoded as an unsigned integer of the given width, see
+    /// [`Dumper::decode_as`]
+    pub fn decode_as(mut self, int_width: Option<IntWidth>) -> DumperConfig {
+        self.options = self.options.decode_as(int_width);
+        self
+    }
+
+    /// Set whether each group of bytes should also be decoded as an unsigned integer of the given width, returning
+    /// an error if the byte group length doesn't match it, see [`Dumper::try_decode_as`]
+    pub fn try_decode_as(self, int_width: Option<IntWidth>) -> Result<DumperConfig, DumperError> {
+        let options = self.options.try_decode_as(int_width)?;
+        Ok(DumperConfig { options })
+    }
+
+    /// Set whether each group of bytes should also be decoded as a float of the given width, see
+    /// [`Dumper::decode_float`]
+    pub fn decode_float(mut self, float_width: Option<FloatWidth>) -> DumperConfig {
+        self.options = self.options.decode_float(float_width);
+        self
+    }
+
+    /// Set whether each group of bytes should also be decoded as a float of the given width, returning an error if
+    /// the byte group length doesn't match it, see [`Dumper::try_decode_float`]
+    pub fn try_decode_float(
+        self,
+        float_width: Option<FloatWidth>,
+    ) -> Result<DumperConfig, DumperError> {
+        let options = self.options.try_decode_float(float_width)?;
+        Ok(DumperConfig { options })
+    }
+
+    /// Set the number of digits printed after the decimal point of decoded floats, see [`Dumper::float_precision`]
+    pub fn float_precision(mut self, float_precision: usize) -> DumperConfig {
+        self.options = self.options.float_precision(float_precision);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn matches_dumper() {
+        let config = Dumper::builder()
+            .line_width(8)
+            .byte_group_length(2)
+            .uppercase(true)
+            .skip(2);
+
+        for bytes in [&b"Lorem ipsum dolor"[..], b"sit amet"] {
+            let expected = Dumper::from_bytes(bytes)
+                .line_width(8)
+                .byte_group_length(2)
+                .uppercase(true)
+                .skip(2)
+                .render()
+                .unwrap();
+
+            let result = config.build_seekable(Cursor::new(bytes)).render().unwrap();
+            assert_eq!(expected, result);
+            let result = config.clone().build(bytes).render().unwrap();
+            assert_eq!(expected, result);
+        }
+    }
+}
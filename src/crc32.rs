@@ -0,0 +1,55 @@
+/// Lookup table for the reflected CRC-32 polynomial `0xedb88320`, indexed by the low byte of the running value
+const TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = match value & 1 {
+                1 => (value >> 1) ^ 0xedb88320,
+                _ => value >> 1,
+            };
+            bit += 1;
+        }
+        table[index] = value;
+        index += 1;
+    }
+    table
+};
+
+/// Running [CRC-32] checksum (as used by zlib, PNG and gzip) of the bytes passed to [`Crc32::update`]
+///
+/// [CRC-32]: https://en.wikipedia.org/wiki/Cyclic_redundancy_check
+pub(crate) struct Crc32 {
+    value: u32,
+}
+
+impl Crc32 {
+    pub(crate) fn new() -> Crc32 {
+        Crc32 { value: !0 }
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.value = TABLE[((self.value ^ byte as u32) & 0xff) as usize] ^ (self.value >> 8);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u32 {
+        !self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_value() {
+        let mut crc = Crc32::new();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(0xcbf43926, crc.finish());
+    }
+}
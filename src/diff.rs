@@ -0,0 +1,293 @@
+use std::io::{self, Read, Write};
+
+use crate::error::DumperError;
+use crate::render::{push_hex_byte, OffsetBase};
+
+/// Options controlling the output of [`diff`]
+#[derive(Clone, Debug)]
+pub struct DiffOptions {
+    line_width: usize,
+    squeeze: bool,
+    color: bool,
+    uppercase: bool,
+    input_length: u64,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        DiffOptions {
+            line_width: 0x10,
+            squeeze: false,
+            color: false,
+            uppercase: false,
+            input_length: 0,
+        }
+    }
+}
+
+impl DiffOptions {
+    /// Construct a new instance of [`DiffOptions`] with the default options
+    pub fn new() -> DiffOptions {
+        DiffOptions::default()
+    }
+
+    /// Set the number of bytes of each input shown per line
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line_width` is outside the range 1-256, see [`DiffOptions::try_line_width`]
+    pub fn line_width(self, line_width: usize) -> DiffOptions {
+        self.try_line_width(line_width)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the number of bytes of each input shown per line, returning an error if it is outside the range 1-256
+    pub fn try_line_width(mut self, line_width: usize) -> Result<DiffOptions, DumperError> {
+        if line_width == 0 || line_width > 256 {
+            return Err(DumperError::InvalidLineWidth(line_width));
+        }
+        self.line_width = line_width;
+        Ok(self)
+    }
+
+    /// Set the length of the longer input, if it is known, so the offset column can be made wide enough up front
+    ///
+    /// The offset column is 8 hex digits wide, or 16 if the inputs are longer than 4 GiB. Without a length, the
+    /// column only widens once an offset past 4 GiB is reached, so later lines are no longer aligned with earlier
+    /// ones.
+    pub fn input_length(mut self, input_length: u64) -> DiffOptions {
+        self.input_length = input_length;
+        self
+    }
+
+    /// Set whether runs of lines that are identical in both inputs should be collapsed
+    ///
+    /// Like [`Dumper::squeeze`](crate::Dumper::squeeze), the first line of each run is printed and the rest are
+    /// replaced by a single `*` line, except for the last line of the inputs, which is always printed so the final
+    /// offset can be seen.
+    pub fn squeeze(mut self, squeeze: bool) -> DiffOptions {
+        self.squeeze = squeeze;
+        self
+    }
+
+    /// Set whether differing bytes should be highlighted in red using ANSI escape sequences
+    pub fn color(mut self, color: bool) -> DiffOptions {
+        self.color = color;
+        self
+    }
+
+    /// Set whether hex digits should be printed in uppercase
+    pub fn uppercase(mut self, uppercase: bool) -> DiffOptions {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Format the bytes of one input as a hex column and an ASCII column, padded to the full line width
+    fn format_side(&self, bytes: &[u8], other: &[u8]) -> (String, String) {
+        let highlight = |index: usize, text: String| match self.color
+            && other.get(index) != Some(&bytes[index])
+        {
+            true => format!("\x1b[31m{text}\x1b[0m"),
+            false => text,
+        };
+
+        let hex = (0..bytes.len())
+            .map(|index| {
+                let mut text = String::with_capacity(2);
+                push_hex_byte(&mut text, bytes[index], self.uppercase);
+                highlight(index, text)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let ascii = (0..bytes.len())
+            .map(|index| {
+                let character = match bytes[index] {
+                    byte @ 0x20..=0x7e => byte as char,
+                    _ => '.',
+                };
+                highlight(index, character.to_string())
+            })
+            .collect::<String>();
+
+        // escape sequences take up no space on screen, so pad based on the number of visible characters
+        let hex_length = (3 * bytes.len()).saturating_sub(1);
+        let hex_padding = " ".repeat(3 * self.line_width - 1 - hex_length);
+        let ascii_padding = " ".repeat(self.line_width - bytes.len());
+
+        (hex + &hex_padding, ascii + &ascii_padding)
+    }
+
+    fn format_line(&self, offset: u64, line_a: &[u8], line_b: &[u8]) -> String {
+        let common_length = line_a.len().min(line_b.len());
+        let marker = if line_a[..common_length] != line_b[..common_length] {
+            '!'
+        } else if line_a.len() > line_b.len() {
+            '<'
+        } else if line_a.len() < line_b.len() {
+            '>'
+        } else {
+            ' '
+        };
+
+        let (hex_a, ascii_a) = self.format_side(line_a, line_b);
+        let (hex_b, ascii_b) = self.format_side(line_b, line_a);
+
+        let width = OffsetBase::Hex.width(self.input_length.saturating_sub(1).max(offset));
+        let line = format!("{offset:0width$x} {marker} {hex_a} | {ascii_a} | {hex_b} | {ascii_b}");
+        line.trim_end().to_string()
+    }
+}
+
+/// Dump `a` and `b` side by side to `writer`, one line per offset
+///
+/// Each line starts with a marker column: `!` if the inputs differ on that line, `<` or `>` if only `a` or `b`
+/// respectively has bytes left there, or a space if the line is identical. The shorter input is padded with spaces
+/// so both sides stay aligned by offset.
+pub fn diff<A: Read, B: Read, W: Write>(
+    mut a: A,
+    mut b: B,
+    writer: &mut W,
+    opts: DiffOptions,
+) -> io::Result<()> {
+    let mut offset: u64 = 0;
+    let mut squeezing = false;
+    // the latest line left out of the current run, printed if it turns out to be the last line of the inputs
+    let mut squeezed_line: Option<(u64, Vec<u8>)> = None;
+    let mut squeeze_marker_printed = false;
+
+    loop {
+        let line_a = read_line(&mut a, opts.line_width)?;
+        let line_b = read_line(&mut b, opts.line_width)?;
+        if line_a.is_empty() && line_b.is_empty() {
+            if let Some((offset, line)) = squeezed_line {
+                writeln!(writer, "{}", opts.format_line(offset, &line, &line))?;
+            }
+            break;
+        }
+
+        let line_offset = offset;
+        offset = offset.saturating_add(opts.line_width as u64);
+
+        if opts.squeeze && line_a == line_b {
+            if squeezing {
+                let run_continues = squeezed_line.replace((line_offset, line_a)).is_some();
+                if run_continues && !squeeze_marker_printed {
+                    writeln!(writer, "*")?;
+                    squeeze_marker_printed = true;
+                }
+                continue;
+            }
+            squeezing = true;
+        } else {
+            if squeezed_line.take().is_some() && !squeeze_marker_printed {
+                writeln!(writer, "*")?;
+            }
+            squeezing = false;
+            squeeze_marker_printed = false;
+        }
+
+        writeln!(
+            writer,
+            "{}",
+            opts.format_line(line_offset, &line_a, &line_b)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Read up to `line_width` bytes from `reader`, returning fewer only at the end of the input
+fn read_line<R: Read>(reader: &mut R, line_width: usize) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0; line_width];
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(length) => filled += length,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    buf.truncate(filled);
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn side_by_side() {
+        let expected = "00000000   48 65 6c 6c | Hell | 48 65 6c 6c | Hell\n\
+                        00000004 ! 6f 2c 20 77 | o, w | 6f 21 20 77 | o! w\n\
+                        00000008 < 6f 72       | or   |             |";
+
+        let mut output = Vec::new();
+        diff(
+            &b"Hello, wor"[..],
+            &b"Hello! w"[..],
+            &mut output,
+            DiffOptions::new().line_width(4),
+        )
+        .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap().trim_end());
+    }
+
+    #[test]
+    fn squeezed() {
+        let expected = "00000000   00 00 | .. | 00 00 | ..\n\
+                        *\n\
+                        00000008 ! 00 01 | .. | 00 02 | ..\n\
+                        0000000a   00 00 | .. | 00 00 | ..\n\
+                        *\n\
+                        00000010 >       |    | 00 00 | ..";
+
+        let a = [0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        let b = [0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut output = Vec::new();
+        diff(
+            &a[..],
+            &b[..],
+            &mut output,
+            DiffOptions::new().line_width(2).squeeze(true),
+        )
+        .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap().trim_end());
+
+        let expected = "00000000   00 00 | .. | 00 00 | ..\n\
+                        *\n\
+                        00000006   00 00 | .. | 00 00 | ..";
+
+        let mut output = Vec::new();
+        diff(
+            &[0; 8][..],
+            &[0; 8][..],
+            &mut output,
+            DiffOptions::new().line_width(2).squeeze(true),
+        )
+        .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap().trim_end());
+    }
+
+    #[test]
+    fn offset_width() {
+        let expected = "0000000000000000   41 | A | 41 | A";
+
+        let mut output = Vec::new();
+        diff(
+            &b"A"[..],
+            &b"A"[..],
+            &mut output,
+            DiffOptions::new()
+                .line_width(1)
+                .input_length(u32::MAX as u64 + 2),
+        )
+        .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap().trim_end());
+    }
+}
@@ -0,0 +1,3884 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Cursor, IsTerminal, Read, Seek, SeekFrom, Write},
+    mem,
+    ops::Range,
+    process,
+    sync::Arc,
+};
+use unicode_width::UnicodeWidthChar;
+
+use crate::crc32::Crc32;
+use crate::{
+    ByteStyle, ChecksumKind, ClassicRenderer, ColorChoice, DumperError, Endian, FloatWidth,
+    IntWidth, Layout, LineRenderer, OffsetBase, Radix, SeparatorStyle, StreamDumper, TextEncoding,
+    TextMode, WritingDumper,
+};
+pub struct Dumper<R>
+where
+    R: Read,
+{
+    reader: R,
+    /// Measures the remaining length of the input, if the reader supports it
+    input_length: fn(&mut R) -> io::Result<Option<u64>>,
+    /// Advances the reader past the skipped bytes, by seeking if the reader supports it
+    skip_input: fn(&mut R, u64) -> io::Result<()>,
+    classic: ClassicRenderer,
+    renderer: Option<Box<dyn LineRenderer>>,
+    skip: u64,
+    color_choice: ColorChoice,
+    base_address: u64,
+    /// Whether the printed offsets count from the first byte dumped rather than from the start of the input
+    relative_offsets: bool,
+    line_count: Option<usize>,
+    byte_count: Option<u64>,
+    squeeze: bool,
+    squeeze_count: bool,
+    /// Number of lines after which a blank line is printed
+    block_spacing: Option<usize>,
+    grep: Option<Vec<u8>>,
+    highlight: Option<Vec<u8>>,
+    annotations: Vec<(Range<u64>, String)>,
+    summary: bool,
+    histogram: bool,
+    collapse_fill: Option<(u8, usize)>,
+    /// Number of lines of the input each printed line stands for when sampling
+    stride: usize,
+    line_width: usize,
+    line_width_auto: bool,
+    line_terminator: LineTerminator,
+    trailing_newline: bool,
+}
+
+/// Sequence of characters ending each line of the output
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineTerminator {
+    /// `\n`, as used on Unix
+    #[default]
+    Lf,
+    /// `\r\n`, as used on Windows
+    CrLf,
+}
+
+impl LineTerminator {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineTerminator::Lf => "\n",
+            LineTerminator::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Writes lines separated by a terminator, leaving it out after the last line unless every line is terminated
+struct TerminatedLines<'a, W: Write> {
+    writer: &'a mut W,
+    terminator: &'static str,
+    /// Whether the terminator is written right after each line, rather than only once the next line comes
+    terminate_each: bool,
+    started: bool,
+}
+
+impl<W: Write> TerminatedLines<'_, W> {
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if self.started && !self.terminate_each {
+            self.writer.write_all(self.terminator.as_bytes())?;
+        }
+        self.started = true;
+        self.writer.write_all(line.as_bytes())?;
+        if self.terminate_each {
+            self.writer.write_all(self.terminator.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Progress through the input while a dump is being read
+struct ReadState {
+    first_offset: u64,
+    chunk_offset: u64,
+    remaining: Option<u64>,
+    lines_read: usize,
+    bytes_read: u64,
+    /// Number of bytes passed to the renderer, which leaves out the bytes of lines that weren't printed
+    bytes_dumped: u64,
+    crc32: Crc32,
+    /// Number of times each byte value has been read, if a histogram is printed
+    byte_counts: Option<Box<[u64; 256]>>,
+    /// Bytes of the most recently read line, used to detect repeated lines when squeezing
+    previous_bytes: Option<Vec<u8>>,
+    /// The latest line of a squeezed run, printed if it turns out to be the last line of the input
+    squeezed_line: Option<(u64, Vec<u8>)>,
+    /// Number of lines in the current squeezed run, including the latest one
+    squeezed_lines: u64,
+    squeeze_marker_printed: bool,
+    /// A line that is ready to be printed after the squeeze marker
+    pending_line: Option<String>,
+    /// Number of lines of the body printed so far, which are spaced into blocks
+    body_lines: usize,
+    /// A line that is ready to be printed after the blank line ending a block
+    spaced_line: Option<String>,
+    /// Lines read ahead of the next one to be printed, so that highlighted matches extending past it are known
+    lookahead: VecDeque<(u64, Vec<u8>)>,
+    input_ended: bool,
+    /// Whether the reader has signalled that it is waiting for more bytes since the output was last flushed
+    input_waiting: bool,
+    /// The last bytes read, used to find highlighted matches that straddle lines
+    highlight_tail: Vec<u8>,
+    /// Offsets of the highlighted matches that may overlap lines which haven't been printed yet
+    highlight_ranges: Vec<Range<u64>>,
+    /// Bytes read from the input that haven't been put into a line yet, when collapsing runs of a fill byte
+    carry: Vec<u8>,
+    /// Offsets and lengths of the collapsed runs of the fill byte whose notes haven't been printed yet
+    fill_runs: VecDeque<(u64, u64)>,
+    /// Bytes read from the input in one go, which lines are sliced out of
+    read_buffer: Vec<u8>,
+    /// Index of the first byte in `read_buffer` that hasn't been put into a line yet
+    read_position: usize,
+}
+
+struct Lines<R>
+where
+    R: Read,
+{
+    dumper: Dumper<R>,
+    state: Option<ReadState>,
+    finished: bool,
+}
+
+impl<R> Iterator for Lines<R>
+where
+    R: Read,
+{
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let state = match &mut self.state {
+            Some(state) => state,
+            None => match self.dumper.begin(false) {
+                Ok(state) => self.state.insert(state),
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            },
+        };
+
+        let line = self.dumper.next_line(state).transpose();
+        if !matches!(line, Some(Ok(_))) {
+            self.finished = true;
+        }
+        line
+    }
+}
+
+impl<R> Dumper<R>
+where
+    R: Read,
+{
+    /// Construct a new instance of [`Dumper`]
+    ///
+    /// Since the length of the input isn't known up front, the offset column starts out 8 digits wide and
+    /// [`LineRenderer::begin`] is passed `None`; use [`Dumper::from_seekable`] for readers that can be measured.
+    ///
+    /// A read failing with [`io::ErrorKind::WouldBlock`] ends the current line early, so that the bytes read so far
+    /// are printed while the reader waits for more input, and the next line is shortened so that later lines start
+    /// at multiples of the line width again. Such a read is retried if no bytes of the line have been read yet.
+    pub fn new(reader: R) -> Dumper<R> {
+        Dumper {
+            reader,
+            input_length: unknown_length,
+            skip_input: discard_input,
+            classic: ClassicRenderer::default(),
+            renderer: None,
+            skip: 0,
+            color_choice: ColorChoice::Never,
+            base_address: 0,
+            relative_offsets: false,
+            line_count: None,
+            byte_count: None,
+            squeeze: false,
+            squeeze_count: false,
+            block_spacing: None,
+            grep: None,
+            highlight: None,
+            annotations: Vec::new(),
+            summary: false,
+            histogram: false,
+            collapse_fill: None,
+            stride: 1,
+            line_width: 0x10,
+            line_width_auto: false,
+            line_terminator: LineTerminator::Lf,
+            trailing_newline: true,
+        }
+    }
+
+    /// Set whether [C0 control codes] should be displayed as characters from the [Control Pictures] Unicode block
+    ///
+    /// [C0 control codes]: https://en.wikipedia.org/wiki/C0_and_C1_control_codes
+    /// [Control Pictures]: https://en.wikipedia.org/wiki/Control_Pictures
+    pub fn control_pictures(mut self, control_pictures: bool) -> Dumper<R> {
+        self.classic.control_pictures = control_pictures;
+        self
+    }
+
+    /// Set the character set used to decode the bytes shown in the text column
+    pub fn text_encoding(mut self, text_encoding: TextEncoding) -> Dumper<R> {
+        self.classic.text_encoding = text_encoding;
+        self
+    }
+
+    /// Set a function returning the character shown for each byte in the text column, such as for a custom character
+    /// set
+    ///
+    /// It replaces [`Dumper::text_encoding`], [`Dumper::control_pictures`] and the placeholder character. Characters
+    /// it returns that don't take up a single column (going by their Unicode width), such as wide CJK characters or
+    /// combining marks, are shown as the placeholder character instead so that the text column stays aligned.
+    pub fn text_renderer(
+        mut self,
+        text_renderer: impl Fn(u8) -> char + Send + Sync + 'static,
+    ) -> Dumper<R> {
+        self.classic.text_renderer = Some(Arc::new(text_renderer));
+        self
+    }
+
+    /// Set whether a column with the [Shannon entropy] of each line (between 0 and 8 bits per byte) should be printed
+    /// after the ASCII column, which helps spot compressed or encrypted regions
+    ///
+    /// Since the entropy is computed per line, it can be at most log2 of the line width, e.g. 4 for 16 byte lines.
+    ///
+    /// [Shannon entropy]: https://en.wikipedia.org/wiki/Entropy_(information_theory)
+    pub fn entropy(mut self, entropy: bool) -> Dumper<R> {
+        self.classic.entropy = entropy;
+        self
+    }
+
+    /// Set the checksum of each line printed as a hex byte in a column of its own, or `None` to leave it out
+    ///
+    /// The column is labelled with the kind of checksum in the header, e.g. to compare against dumps from a serial
+    /// port that end each line with one.
+    pub fn line_checksum(mut self, kind: Option<ChecksumKind>) -> Dumper<R> {
+        self.classic.line_checksum = kind;
+        self
+    }
+
+    /// Set the character shown in the text column for bytes that can't be printed
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` isn't a single column wide, see [`Dumper::try_placeholder`]
+    pub fn placeholder(self, placeholder: char) -> Dumper<R> {
+        self.try_placeholder(placeholder)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the character shown in the text column for bytes that can't be printed, returning an error if it isn't a
+    /// single column wide
+    pub fn try_placeholder(mut self, placeholder: char) -> Result<Dumper<R>, DumperError> {
+        if placeholder.width() != Some(1) {
+            return Err(DumperError::InvalidPlaceholder(placeholder));
+        }
+        self.classic.placeholder = placeholder;
+        Ok(self)
+    }
+
+    /// Set how bytes are represented in the text column
+    pub fn text_mode(mut self, text_mode: TextMode) -> Dumper<R> {
+        self.classic.text_mode = text_mode;
+        self
+    }
+
+    /// Set whether hex digits in the offset and byte columns should be printed in uppercase
+    pub fn uppercase(mut self, uppercase: bool) -> Dumper<R> {
+        self.classic.uppercase = uppercase;
+        self
+    }
+
+    /// Set whether bytes should be colored by category (null, printable, control or non-ASCII) using ANSI escape
+    /// sequences, or by the colors chosen with [`Dumper::style_fn`] if it is set
+    pub fn color(self, color: bool) -> Dumper<R> {
+        match color {
+            true => self.color_choice(ColorChoice::Always),
+            false => self.color_choice(ColorChoice::Never),
+        }
+    }
+
+    /// Set whether the dump should be formatted as HTML, inside a `<pre class="rxd">` element
+    ///
+    /// Each byte in the hex and ASCII columns is wrapped in a `<span>` with a class for its category, which is one of
+    /// `rxd-null`, `rxd-printable`, `rxd-control` or `rxd-high`. Color escape sequences are never used in this mode.
+    pub fn html(mut self, html: bool) -> Dumper<R> {
+        self.classic.html = html;
+        self
+    }
+
+    /// Set when bytes should be colored, see [`ColorChoice`]
+    pub fn color_choice(mut self, color_choice: ColorChoice) -> Dumper<R> {
+        self.color_choice = color_choice;
+        self
+    }
+
+    /// Set the numeral system used to print the offset column
+    pub fn offset_base(mut self, offset_base: OffsetBase) -> Dumper<R> {
+        self.classic.offset_base = offset_base;
+        self
+    }
+
+    /// Set the number of digits of every offset, rather than fitting the offset column to the offsets of the dump
+    ///
+    /// Offsets are padded with leading zeros to this width, and dumping fails with
+    /// [`DumperError::OffsetTooWide`] if an offset needs more digits. A width of 0 is treated as 1. It has no effect
+    /// with [`Dumper::offset_formatter`].
+    pub fn offset_width(mut self, digits: Option<usize>) -> Dumper<R> {
+        self.classic.fixed_offset_width = digits.map(|digits| digits.max(1));
+        self
+    }
+
+    /// Set whether each offset should be followed by its decimal value in parentheses, e.g. `00000010 (16)`
+    ///
+    /// The column is as wide as the last offset of the dump needs, with shorter ones padded on the right. It has no
+    /// effect with [`Dumper::offset_formatter`].
+    pub fn offset_both(mut self, enabled: bool) -> Dumper<R> {
+        self.classic.offset_both = enabled;
+        self
+    }
+
+    /// Set the address shown in the offset column for the first byte of the input
+    ///
+    /// Unlike [`Dumper::skip`], this doesn't discard any input, it only shifts the printed offsets.
+    pub fn base_address(mut self, base_address: u64) -> Dumper<R> {
+        self.base_address = base_address;
+        self
+    }
+
+    /// Set the number of lines to print
+    pub fn line_count(mut self, line_count: Option<usize>) -> Dumper<R> {
+        self.line_count = line_count;
+        self
+    }
+
+    /// Set the range of offsets `start..end` of the input to print, as a [`Dumper::skip`] of `start` bytes and a
+    /// [`Dumper::byte_count`] of `end - start` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is before `start`, see [`Dumper::try_range`]
+    pub fn range(self, start: u64, end: u64) -> Dumper<R> {
+        self.try_range(start, end)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the range of offsets `start..end` of the input to print, returning an error if `end` is before `start`
+    pub fn try_range(self, start: u64, end: u64) -> Result<Dumper<R>, DumperError> {
+        if end < start {
+            return Err(DumperError::InvalidRange { start, end });
+        }
+        Ok(self.skip(start).byte_count(Some(end - start)))
+    }
+
+    /// Set the range of offsets of the input to print to the region given to [`Dumper::annotate`] with the label
+    /// `name`, so that a part of a file format can be dumped by name
+    ///
+    /// The annotations must be set first. Since their ranges are offsets as shown in the offset column, the
+    /// [`Dumper::base_address`] set so far is subtracted from them to find the bytes of the input to dump. The
+    /// printed offsets are still those of the input, unless [`Dumper::relative_offsets`] is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no annotation is labelled `name`, see [`Dumper::try_region`]
+    pub fn region(self, name: &str) -> Dumper<R> {
+        self.try_region(name).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the range of offsets of the input to print to the annotated region labelled `name`, returning an error if
+    /// there is no such region
+    pub fn try_region(self, name: &str) -> Result<Dumper<R>, DumperError> {
+        let Some(range) = self
+            .annotations
+            .iter()
+            .find(|(_, label)| label == name)
+            .map(|(range, _)| range.clone())
+        else {
+            return Err(DumperError::UnknownRegion(name.to_string()));
+        };
+
+        let start = range.start.saturating_sub(self.base_address);
+        let end = range.end.saturating_sub(self.base_address);
+        self.try_range(start, end)
+    }
+
+    /// Set whether the printed offsets count from the first byte dumped rather than from the start of the input,
+    /// leaving out the bytes skipped with [`Dumper::skip`], [`Dumper::range`] or [`Dumper::region`]
+    ///
+    /// The offsets still start at the [`Dumper::base_address`]. Annotations are matched against the printed
+    /// offsets, so with relative offsets they only line up with the bytes they label if those were skipped too.
+    pub fn relative_offsets(mut self, relative_offsets: bool) -> Dumper<R> {
+        self.relative_offsets = relative_offsets;
+        self
+    }
+
+    /// Set the total number of bytes to print
+    pub fn byte_count(mut self, byte_count: Option<u64>) -> Dumper<R> {
+        self.byte_count = byte_count;
+        self
+    }
+
+    /// Set whether runs of identical lines should be collapsed into a single `*` line after their first line
+    ///
+    /// The last line of the input is always printed, so that the final offset is visible. Squeezing is off by
+    /// default, so every line is printed unless it is enabled, and lines are only squeezed in the default layout,
+    /// not with custom renderers.
+    pub fn squeeze(mut self, squeeze: bool) -> Dumper<R> {
+        self.squeeze = squeeze;
+        self
+    }
+
+    /// Set whether the `*` line of each squeezed run should also say how many bytes were left out, e.g.
+    /// `*  (0x30 bytes identical)`
+    ///
+    /// The number is only known once the run ends, so the line is printed then rather than as soon as the run is
+    /// found. It has no effect unless [`Dumper::squeeze`] is enabled.
+    pub fn squeeze_count(mut self, squeeze_count: bool) -> Dumper<R> {
+        self.squeeze_count = squeeze_count;
+        self
+    }
+
+    /// Set the number of lines after which a blank line is printed, so that the dump is split into blocks that are
+    /// easier to scan, or `None` (the default) to print the lines without gaps
+    ///
+    /// Blank lines only go between lines, never after the last one. The `*` lines of [`Dumper::squeeze`] and the
+    /// notes of [`Dumper::collapse_fill`] count as lines, while the lines they leave out don't. A block length of
+    /// zero is the same as `None`, and blocks are only spaced in the default layout, not with custom renderers.
+    pub fn block_spacing(mut self, every: Option<usize>) -> Dumper<R> {
+        self.block_spacing = every.filter(|&every| every > 0);
+        self
+    }
+
+    /// Set a byte sequence that lines must contain in order to be printed
+    ///
+    /// Each line is matched on its own, so an occurrence of `needle` that straddles two lines isn't matched.
+    pub fn grep(mut self, needle: Vec<u8>) -> Dumper<R> {
+        self.grep = Some(needle);
+        self
+    }
+
+    /// Set a byte sequence whose occurrences should be highlighted in reverse video, including those that straddle
+    /// lines
+    pub fn highlight(mut self, pattern: Vec<u8>) -> Dumper<R> {
+        self.highlight = Some(pattern);
+        self
+    }
+
+    /// Set labels for ranges of offsets (as shown in the offset column), which are appended as a `; label` comment
+    /// to every line with bytes in the range, such as to document the fields of a file format
+    ///
+    /// When several ranges overlap a line, all of their labels are shown in the order they were given, separated by
+    /// `, `. Annotations only apply to the default layout.
+    pub fn annotate(mut self, ranges: Vec<(Range<u64>, String)>) -> Dumper<R> {
+        self.annotations = ranges;
+        self
+    }
+
+    /// Set whether a line with the number of bytes dumped and their CRC-32 checksum should be printed after the
+    /// dump, e.g. `-- 1234 bytes, crc32=deadbeef`
+    ///
+    /// The summary is only printed by the default layout, not by custom renderers.
+    pub fn summary(mut self, summary: bool) -> Dumper<R> {
+        self.summary = summary;
+        self
+    }
+
+    /// Set whether a histogram of the most common byte values should be printed after the dump, with one line per
+    /// value giving its count, its share of the bytes dumped and a bar scaled to the most common value
+    ///
+    /// Like the summary, the histogram is only printed by the default layout.
+    pub fn histogram(mut self, histogram: bool) -> Dumper<R> {
+        self.histogram = histogram;
+        self
+    }
+
+    /// Set a fill byte whose runs of at least `min_run` bytes are left out of the dump, such as the holes of a
+    /// sparse file
+    ///
+    /// Each run is replaced by a note like `[0x1000 bytes of 0x00 skipped]`, and the dump resumes at the offset of
+    /// the next byte that isn't the fill byte, so lines may be shorter and no longer start at multiples of the line
+    /// width. Unlike [`Dumper::squeeze`], runs are found at any offset rather than only as whole lines.
+    pub fn collapse_fill(mut self, byte: Option<u8>, min_run: usize) -> Dumper<R> {
+        self.collapse_fill = byte.map(|byte| (byte, min_run.max(1)));
+        self
+    }
+
+    /// Set how many lines of the input each printed line stands for, so that only every `every`th line is printed
+    ///
+    /// This is lossy sampling for a quick overview of a large input, not a full dump: after each printed line, the
+    /// bytes of the next `every - 1` lines are skipped (by seeking if the reader was given to
+    /// [`Dumper::from_seekable`]). The printed offsets stay accurate, so paired with [`Dumper::color`] the dump
+    /// shows where the regions of the input change. The skipped bytes count towards [`Dumper::byte_count`] but not
+    /// [`Dumper::line_count`]. A stride of 0 is treated as 1, and sampling doesn't apply with
+    /// [`Dumper::collapse_fill`].
+    pub fn stride(mut self, every: usize) -> Dumper<R> {
+        self.stride = every.max(1);
+        self
+    }
+
+    /// Set the number of bytes per line to be printed
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line_width` is outside the range 1-256, see [`Dumper::try_line_width`]
+    pub fn line_width(self, line_width: usize) -> Dumper<R> {
+        self.try_line_width(line_width)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the number of bytes per line to be printed, returning an error if it is outside the range 1-256
+    pub fn try_line_width(mut self, line_width: usize) -> Result<Dumper<R>, DumperError> {
+        if line_width == 0 || line_width > 256 {
+            return Err(DumperError::InvalidLineWidth(line_width));
+        }
+        self.line_width = line_width;
+        self.classic.line_width = line_width;
+        Ok(self)
+    }
+
+    /// Set whether the line width should be chosen to fit the terminal, overriding [`Dumper::line_width`]
+    ///
+    /// When dumping to a terminal with [`Dumper::dump`], the line width is the largest one (preferring a multiple of
+    /// the byte group length) whose lines fit in the terminal's columns, or in the `COLUMNS` environment variable if
+    /// the terminal can't be queried. Otherwise, including when the output isn't a terminal, 16 bytes are printed
+    /// per line. Only the layout of the default renderer is taken into account.
+    pub fn line_width_auto(mut self, line_width_auto: bool) -> Dumper<R> {
+        self.line_width_auto = line_width_auto;
+        self
+    }
+
+    /// Set whether the offset column should be printed at the start of every line, and above it in the header
+    pub fn show_offset(mut self, show_offset: bool) -> Dumper<R> {
+        self.classic.show_offset = show_offset;
+        self
+    }
+
+    /// Set whether the text column should be printed after the data column, and above it in the header
+    pub fn show_ascii(mut self, show_ascii: bool) -> Dumper<R> {
+        self.classic.show_ascii = show_ascii;
+        self
+    }
+
+    /// Set the maximum number of bytes of each line shown in the text column, which otherwise shows every byte of
+    /// the line
+    ///
+    /// The data column still shows all of them, so that wide lines don't make the text column unwieldy. A width of
+    /// 0 is treated as 1, see [`Dumper::show_ascii`] to leave the column out instead.
+    pub fn ascii_width(mut self, ascii_width: Option<usize>) -> Dumper<R> {
+        self.classic.ascii_width = ascii_width.map(|ascii_width| ascii_width.max(1));
+        self
+    }
+
+    /// Set the text shown in the data column in place of each byte missing from the last line, when it is shorter
+    /// than the line width
+    ///
+    /// By default the missing bytes are left blank. The filler is padded with spaces to the width of a byte, e.g.
+    /// `Some("--")` shows `48 69 -- --` for a short hex line of 4 bytes. The text column still only shows the bytes
+    /// that are there.
+    pub fn short_line_filler(mut self, filler: Option<&str>) -> Dumper<R> {
+        self.classic.short_line_filler = filler.map(String::from);
+        self
+    }
+
+    /// Set the separators placed between columns, from which the separator row below the header is derived
+    pub fn separators(mut self, separators: SeparatorStyle) -> Dumper<R> {
+        self.classic.separators = separators;
+        self
+    }
+
+    /// Set whether the row of byte offsets and the separator row below it should be printed before the first line
+    ///
+    /// Leaving them out suits dumps that are piped into other tools or concatenated, like the lines returned by
+    /// [`Dumper::lines`]. The header of a custom renderer, which is usually needed for its output to be
+    /// valid, is still printed.
+    pub fn header(mut self, show: bool) -> Dumper<R> {
+        self.classic.show_header = show;
+        self
+    }
+
+    /// Set whether the dump should be printed with as little whitespace as possible
+    ///
+    /// In compact mode the header and separator row are left out, columns are separated by a single space instead of
+    /// [`Dumper::separators`], and short lines aren't padded, so only full lines stay aligned.
+    pub fn compact(mut self, compact: bool) -> Dumper<R> {
+        self.classic.compact = compact;
+        self
+    }
+
+    /// Set whether each line should start with its 1-based line number within the dump, before the offset column
+    ///
+    /// Lines skipped by [`Dumper::squeeze`] or [`Dumper::grep`] are still counted, and the column is as wide as the
+    /// number of lines to be dumped if the length of the input is known.
+    pub fn line_numbers(mut self, line_numbers: bool) -> Dumper<R> {
+        self.classic.line_numbers = line_numbers;
+        self
+    }
+
+    /// Set the characters ending each line written by [`Dumper::dump`] and [`Dumper::dump_to`], and separating the
+    /// lines returned by [`Dumper::render`]
+    pub fn line_terminator(mut self, line_terminator: LineTerminator) -> Dumper<R> {
+        self.line_terminator = line_terminator;
+        self
+    }
+
+    /// Set whether [`Dumper::dump`] and [`Dumper::dump_to`] should end the last line with the line terminator too
+    ///
+    /// This is `true` by default, so that the dump ends like any other text printed to a terminal. [`Dumper::render`]
+    /// never adds a terminator after the last line, so it returns the same as [`Dumper::dump_to`] does with this set
+    /// to `false`.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Dumper<R> {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Set a function choosing the colors of each byte from its offset and value, replacing the colors of the byte
+    /// categories given by [`ByteStyle::of_category`], such as to mark the bytes selected in an editor
+    ///
+    /// It only applies when the output is colored (see [`Dumper::color_choice`]), and not in [`Dumper::html`] mode.
+    /// The offset is the one shown for the byte, which includes the [`Dumper::base_address`]. Highlighted matches
+    /// are still shown in reverse video on top of the style.
+    pub fn style_fn(
+        mut self,
+        style_fn: impl Fn(u64, u8) -> ByteStyle + Send + Sync + 'static,
+    ) -> Dumper<R> {
+        self.classic.style_fn = Some(Arc::new(style_fn));
+        self
+    }
+
+    /// Set a function formatting the offset column, replacing [`Dumper::offset_base`], such as for segmented
+    /// addresses
+    ///
+    /// The column is as wide as the longest of the formatted first and last offsets, and shorter offsets are padded
+    /// on the left. If the length of the input isn't known up front, the function should return strings of the same
+    /// width for every offset to keep the column aligned.
+    pub fn offset_formatter(
+        mut self,
+        offset_formatter: impl Fn(u64) -> String + Send + Sync + 'static,
+    ) -> Dumper<R> {
+        self.classic.offset_formatter = Some(Arc::new(offset_formatter));
+        self
+    }
+
+    /// Set the numeral system used to print each byte in the data column
+    pub fn radix(mut self, radix: Radix) -> Dumper<R> {
+        self.classic.radix = radix;
+        self
+    }
+
+    /// Set the number of bytes after which an extra space is inserted between groups, like the gap in the middle of
+    /// each line of `hexdump -C`
+    ///
+    /// The gaps only go between groups, so `every` should be a multiple of [`Dumper::byte_group_length`]. `None`
+    /// (the default) or `Some(0)` leaves them out.
+    pub fn group_gap(mut self, every: Option<usize>) -> Dumper<R> {
+        self.classic.group_gap = every.filter(|&every| every > 0);
+        self
+    }
+
+    /// Set the byte order used to display each group of bytes, see [`Dumper::byte_group_length`]
+    pub fn endian(mut self, endian: Endian) -> Dumper<R> {
+        self.classic.endian = endian;
+        self
+    }
+
+    /// Set whether the bytes of each line should be shown in reverse order, last byte first, in the data and text
+    /// columns, unlike [`Endian::Little`] which only reverses the bytes within each group
+    ///
+    /// The offset still labels the start of the line, so it is the offset of the last byte printed rather than the
+    /// first, while the labels in the header are reversed along with the bytes. A short last line is reversed on its
+    /// own, starting with the last byte of the input. The other columns are computed from the bytes in input order.
+    pub fn reverse_line(mut self, enabled: bool) -> Dumper<R> {
+        self.classic.reverse_line = enabled;
+        self
+    }
+
+    /// Set the number of bytes grouped together (groups are separated by a space) per line to be printed
+    ///
+    /// A group length larger than the line width is clamped to the line width when dumping, so each line is then a
+    /// single group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte_group_length` is outside the range 1-256, see [`Dumper::try_byte_group_length`]
+    pub fn byte_group_length(self, byte_group_length: usize) -> Dumper<R> {
+        self.try_byte_group_length(byte_group_length)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set the number of bytes grouped together per line to be printed, returning an error if it is outside the
+    /// range 1-256
+    pub fn try_byte_group_length(
+        mut self,
+        byte_group_length: usize,
+    ) -> Result<Dumper<R>, DumperError> {
+        if byte_group_length == 0 || byte_group_length > 256 {
+            return Err(DumperError::InvalidByteGroupLength(byte_group_length));
+        }
+        self.classic.byte_group_length = byte_group_length;
+        Ok(self)
+    }
+
+    /// Set whether each group of bytes should also be decoded as an unsigned integer of the given width, shown in
+    /// decimal in an extra column after the ASCII column
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte group length doesn't match `int_width`, see [`Dumper::try_decode_as`]
+    pub fn decode_as(self, int_width: Option<IntWidth>) -> Dumper<R> {
+        self.try_decode_as(int_width)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set whether each group of bytes should also be decoded as an unsigned integer of the given width, returning an
+    /// error if the byte group length (which must be set first) doesn't match `int_width`
+    pub fn try_decode_as(mut self, int_width: Option<IntWidth>) -> Result<Dumper<R>, DumperError> {
+        if let Some(int_width) = int_width {
+            let byte_group_length = self.classic.byte_group_length;
+            if byte_group_length != int_width.bytes() {
+                return Err(DumperError::IntWidthMismatch {
+                    int_width,
+                    byte_group_length,
+                });
+            }
+        }
+        self.classic.decode_as = int_width;
+        Ok(self)
+    }
+
+    /// Set whether each group of bytes should also be decoded as an IEEE 754 float of the given width, shown in an
+    /// extra column after the integer column, in the byte order set by [`Dumper::endian`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte group length doesn't match `float_width`, see [`Dumper::try_decode_float`]
+    pub fn decode_float(self, float_width: Option<FloatWidth>) -> Dumper<R> {
+        self.try_decode_float(float_width)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Set whether each group of bytes should also be decoded as an IEEE 754 float of the given width, returning an
+    /// error if the byte group length (which must be set first) doesn't match `float_width`
+    pub fn try_decode_float(
+        mut self,
+        float_width: Option<FloatWidth>,
+    ) -> Result<Dumper<R>, DumperError> {
+        if let Some(float_width) = float_width {
+            let byte_group_length = self.classic.byte_group_length;
+            if byte_group_length != float_width.bytes() {
+                return Err(DumperError::FloatWidthMismatch {
+                    float_width,
+                    byte_group_length,
+                });
+            }
+        }
+        self.classic.decode_float = float_width;
+        Ok(self)
+    }
+
+    /// Set the number of digits printed after the decimal point of the floats decoded by [`Dumper::decode_float`]
+    /// (5 by default)
+    pub fn float_precision(mut self, float_precision: usize) -> Dumper<R> {
+        self.classic.float_precision = float_precision;
+        self
+    }
+
+    /// Set the number of bytes at the start of the input to skip before dumping
+    ///
+    /// The skipped bytes are read and discarded, unless the dumper was constructed with [`Dumper::from_seekable`] in
+    /// which case the reader seeks past them. Unlike [`Dumper::start_offset`], the printed offsets begin at the
+    /// skipped amount rather than at zero.
+    pub fn skip(mut self, bytes: u64) -> Dumper<R> {
+        self.skip = bytes;
+        self
+    }
+
+    /// Set a custom renderer to format the lines of the dump instead of the default [`ClassicRenderer`]
+    ///
+    /// Options that only affect the layout of the classic renderer (such as [`Dumper::uppercase`]) are ignored by
+    /// custom renderers, while options about which bytes are dumped still apply.
+    pub fn with_renderer(mut self, renderer: Box<dyn LineRenderer>) -> Dumper<R> {
+        self.renderer = Some(renderer);
+        self
+    }
+
+    /// A new [`Dumper`] reading from `reader` with the same options as this one, except for a custom renderer
+    pub(crate) fn copy_options<S: Read>(&self, reader: S) -> Dumper<S> {
+        Dumper {
+            reader,
+            input_length: unknown_length,
+            skip_input: discard_input,
+            classic: self.classic.clone(),
+            renderer: None,
+            skip: self.skip,
+            color_choice: self.color_choice,
+            base_address: self.base_address,
+            relative_offsets: self.relative_offsets,
+            line_count: self.line_count,
+            byte_count: self.byte_count,
+            squeeze: self.squeeze,
+            squeeze_count: self.squeeze_count,
+            block_spacing: self.block_spacing,
+            grep: self.grep.clone(),
+            highlight: self.highlight.clone(),
+            annotations: self.annotations.clone(),
+            summary: self.summary,
+            histogram: self.histogram,
+            collapse_fill: self.collapse_fill,
+            stride: self.stride,
+            line_width: self.line_width,
+            line_width_auto: self.line_width_auto,
+            line_terminator: self.line_terminator,
+            trailing_newline: self.trailing_newline,
+        }
+    }
+
+    fn renderer(&self) -> &dyn LineRenderer {
+        self.renderer.as_deref().unwrap_or(&self.classic)
+    }
+
+    /// Fill `buf` from the read buffer, refilling it from the reader as needed and stopping early only at the end
+    /// of the input
+    ///
+    /// At most `limit` bytes past the ones already buffered are read from the reader, so that the reader isn't
+    /// advanced beyond the last byte to be dumped.
+    fn read_line_bytes(
+        &mut self,
+        state: &mut ReadState,
+        buf: &mut [u8],
+        limit: Option<u64>,
+    ) -> io::Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            if state.read_position == state.read_buffer.len() {
+                let refill_length = match limit {
+                    Some(limit) => limit
+                        .saturating_sub(filled as u64)
+                        .min(READ_BUFFER_LENGTH as u64) as usize,
+                    None => READ_BUFFER_LENGTH,
+                };
+                // never ask for fewer bytes than `buf` is still missing, which would look like the end of the input
+                let refill_length = refill_length.max(buf.len() - filled);
+                state.read_buffer.resize(refill_length, 0);
+                state.read_position = 0;
+                // a read interrupted by a signal hasn't read anything and can just be retried
+                let length = loop {
+                    match self.reader.read(&mut state.read_buffer) {
+                        Ok(length) => break length,
+                        Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            state.input_waiting = true;
+                            if filled > 0 {
+                                state.read_buffer.clear();
+                                return Ok(filled);
+                            }
+                        }
+                        Err(err) => {
+                            state.read_buffer.clear();
+                            return Err(err);
+                        }
+                    }
+                };
+                state.read_buffer.truncate(length);
+                if length == 0 {
+                    break;
+                }
+            }
+
+            let available = &state.read_buffer[state.read_position..];
+            let length = available.len().min(buf.len() - filled);
+            buf[filled..filled + length].copy_from_slice(&available[..length]);
+            state.read_position += length;
+            filled += length;
+        }
+        Ok(filled)
+    }
+
+    /// Prepare to read a dump: resolve the options that depend on the output or the input, and skip to the first
+    /// byte to be dumped
+    fn begin(&mut self, is_terminal: bool) -> io::Result<ReadState> {
+        self.classic.color = self.color_choice.enabled(is_terminal);
+
+        if self.skip > 0 {
+            (self.skip_input)(&mut self.reader, self.skip)?;
+        }
+
+        let mut dumped_length = (self.input_length)(&mut self.reader)?;
+        let first_offset = match self.relative_offsets {
+            true => Some(self.base_address),
+            false => self.base_address.checked_add(self.skip),
+        }
+        .ok_or_else(offset_overflow)?;
+
+        if self.line_width_auto {
+            let last_offset = last_offset(first_offset, dumped_length)?;
+            self.classic.fit_offset_width(first_offset, last_offset);
+            match terminal_columns().filter(|_| is_terminal) {
+                Some(columns) => self.classic.fit_line_width(columns),
+                None => self.classic.line_width = 0x10,
+            }
+            self.line_width = self.classic.line_width;
+        }
+
+        if let Some(length) = dumped_length.as_mut() {
+            if let Some(byte_count) = self.byte_count {
+                *length = (*length).min(byte_count);
+            }
+            if let Some(line_count) = self.line_count {
+                *length = (*length).min((line_count as u64).saturating_mul(self.line_width as u64));
+            }
+        }
+
+        let last_offset = last_offset(first_offset, dumped_length)?;
+        self.classic.fit_offset_width(first_offset, last_offset);
+        check_offset_fits(&self.classic, last_offset)?;
+        self.classic.line_number_width = match dumped_length {
+            Some(length) => length
+                .div_ceil(self.line_width as u64)
+                .max(1)
+                .to_string()
+                .len(),
+            None => 6,
+        };
+
+        // lines left out by grep, sampling or collapsed runs of a fill byte mean the length isn't known up front
+        let filters_lines = self.grep.is_some() || self.stride > 1 || self.collapse_fill.is_some();
+        if let Some(renderer) = self.renderer.as_mut() {
+            renderer.begin(dumped_length.filter(|_| !filters_lines));
+        }
+
+        Ok(ReadState {
+            first_offset,
+            chunk_offset: first_offset,
+            remaining: self.byte_count,
+            lines_read: 0,
+            bytes_read: 0,
+            bytes_dumped: 0,
+            crc32: Crc32::new(),
+            byte_counts: self.histogram.then(|| Box::new([0; 256])),
+            previous_bytes: None,
+            squeezed_line: None,
+            squeezed_lines: 0,
+            squeeze_marker_printed: false,
+            pending_line: None,
+            body_lines: 0,
+            spaced_line: None,
+            lookahead: VecDeque::new(),
+            input_ended: false,
+            input_waiting: false,
+            highlight_tail: Vec::new(),
+            highlight_ranges: Vec::new(),
+            carry: Vec::new(),
+            fill_runs: VecDeque::new(),
+            read_buffer: Vec::new(),
+            read_position: 0,
+        })
+    }
+
+    /// Read the bytes of the next line along with their offset, returning `None` once the input or a limit is
+    /// exhausted
+    fn read_next_line(&mut self, state: &mut ReadState) -> io::Result<Option<(u64, Vec<u8>)>> {
+        let Some(pattern_length) = self.highlight.as_ref().map(Vec::len) else {
+            // a line may have been put back to print a note before it
+            if let Some(line) = state.lookahead.pop_front() {
+                return Ok(Some(line));
+            }
+            return self.read_input_line(state);
+        };
+
+        // read ahead until every match that overlaps the next line has been found
+        while !state.input_ended
+            && (state.lookahead.is_empty()
+                || state
+                    .lookahead
+                    .iter()
+                    .skip(1)
+                    .map(|(_, bytes)| bytes.len())
+                    .sum::<usize>()
+                    < pattern_length.saturating_sub(1))
+        {
+            match self.read_input_line(state)? {
+                Some((chunk_offset, line_bytes)) => {
+                    self.find_highlights(state, chunk_offset, &line_bytes);
+                    state.lookahead.push_back((chunk_offset, line_bytes));
+                }
+                None => state.input_ended = true,
+            }
+        }
+
+        let line = state.lookahead.pop_front();
+        if let Some((chunk_offset, _)) = &line {
+            state
+                .highlight_ranges
+                .retain(|range| range.end > *chunk_offset);
+        }
+        Ok(line)
+    }
+
+    /// Record the offsets of the occurrences of the highlighted pattern that end within `line_bytes`
+    fn find_highlights(&self, state: &mut ReadState, chunk_offset: u64, line_bytes: &[u8]) {
+        let Some(pattern) = self
+            .highlight
+            .as_deref()
+            .filter(|pattern| !pattern.is_empty())
+        else {
+            return;
+        };
+
+        let tail_length = state.highlight_tail.len();
+        let mut window = std::mem::take(&mut state.highlight_tail);
+        window.extend_from_slice(line_bytes);
+        let window_offset = chunk_offset - tail_length as u64;
+
+        for (index, candidate) in window.windows(pattern.len()).enumerate() {
+            // matches that end within the tail were found along with the previous line
+            if index + pattern.len() > tail_length && candidate == pattern {
+                let start = window_offset + index as u64;
+                state
+                    .highlight_ranges
+                    .push(start..start + pattern.len() as u64);
+            }
+        }
+
+        let tail_length = window.len().min(pattern.len() - 1);
+        state.highlight_tail = window.split_off(window.len() - tail_length);
+    }
+
+    /// Format a line with the current renderer, marking the bytes that are part of a highlighted match
+    fn format_line(
+        &mut self,
+        state: &mut ReadState,
+        chunk_offset: u64,
+        line_bytes: &[u8],
+    ) -> io::Result<String> {
+        check_offset_fits(&self.classic, chunk_offset)?;
+        if self.highlight.is_some() {
+            self.classic.highlighted = (chunk_offset..chunk_offset + line_bytes.len() as u64)
+                .map(|offset| {
+                    state
+                        .highlight_ranges
+                        .iter()
+                        .any(|range| range.contains(&offset))
+                })
+                .collect();
+            // the renderer looks up each byte by the position it is printed at
+            if self.classic.reverse_line {
+                self.classic.highlighted.reverse();
+            }
+        }
+        if !self.annotations.is_empty() {
+            let line_end = chunk_offset + line_bytes.len() as u64;
+            let labels: Vec<&str> = self
+                .annotations
+                .iter()
+                .filter(|(range, _)| range.start < line_end && chunk_offset < range.end)
+                .map(|(_, label)| label.as_str())
+                .collect();
+            self.classic.annotation = (!labels.is_empty()).then(|| labels.join(", "));
+        }
+        self.classic.line_number = (chunk_offset - state.first_offset) / self.line_width as u64 + 1;
+        state.bytes_dumped += line_bytes.len() as u64;
+        Ok(self.renderer().render_line(chunk_offset, line_bytes))
+    }
+
+    /// Read the next line directly from the input, applying the line and byte limits
+    fn read_input_line(&mut self, state: &mut ReadState) -> io::Result<Option<(u64, Vec<u8>)>> {
+        if let Some(line_count) = self.line_count {
+            if state.lines_read >= line_count {
+                return Ok(None);
+            }
+        }
+
+        if let Some((fill, min_run)) = self.collapse_fill {
+            return self.read_collapsed_line(state, fill, min_run);
+        }
+
+        // a line that was ended early leaves the rest of its width to the next one
+        let line_width = self.line_width
+            - ((state.chunk_offset - state.first_offset) % self.line_width as u64) as usize;
+        let read_length = match state.remaining {
+            Some(remaining) => remaining.min(line_width as u64) as usize,
+            None => line_width,
+        };
+
+        // the lines left to read also limit how far ahead the reader may be read
+        let limit = match self.line_count {
+            Some(line_count) => {
+                let line_limit =
+                    ((line_count - state.lines_read) as u64).saturating_mul(self.line_width as u64);
+                Some(
+                    state
+                        .remaining
+                        .map_or(line_limit, |remaining| remaining.min(line_limit)),
+                )
+            }
+            None => state.remaining,
+        };
+
+        let mut line_bytes = vec![0u8; read_length];
+        let length = self.read_line_bytes(state, &mut line_bytes, limit)?;
+        if length == 0 {
+            return Ok(None);
+        }
+        line_bytes.truncate(length);
+
+        if let Some(remaining) = state.remaining.as_mut() {
+            *remaining -= length as u64;
+        }
+
+        let chunk_offset = state.chunk_offset;
+        state.lines_read += 1;
+        consume_bytes(state, &line_bytes)?;
+        if self.stride > 1 {
+            self.skip_stride(state)?;
+        }
+
+        Ok(Some((chunk_offset, line_bytes)))
+    }
+
+    /// Skip the lines left out after a printed line when sampling, see [`Dumper::stride`]
+    fn skip_stride(&mut self, state: &mut ReadState) -> io::Result<()> {
+        let mut length = (self.stride as u64 - 1).saturating_mul(self.line_width as u64);
+        if let Some(remaining) = state.remaining.as_mut() {
+            length = length.min(*remaining);
+            *remaining -= length;
+        }
+
+        // the bytes that have already been read ahead are skipped first
+        let buffered = (state.read_buffer.len() - state.read_position).min(length as usize);
+        state.read_position += buffered;
+        let unbuffered = length - buffered as u64;
+        if unbuffered > 0 {
+            (self.skip_input)(&mut self.reader, unbuffered)?;
+        }
+
+        state.chunk_offset = state
+            .chunk_offset
+            .checked_add(length)
+            .ok_or_else(offset_overflow)?;
+        // matches can't straddle the skipped bytes
+        state.highlight_tail.clear();
+        Ok(())
+    }
+
+    /// Read the next line from the input, leaving out the runs of the fill byte that are at least `min_run` bytes
+    /// long and recording them in [`ReadState::fill_runs`]
+    fn read_collapsed_line(
+        &mut self,
+        state: &mut ReadState,
+        fill: u8,
+        min_run: usize,
+    ) -> io::Result<Option<(u64, Vec<u8>)>> {
+        loop {
+            self.fill_carry(state, self.line_width)?;
+            let mut line_length = state.carry.len().min(self.line_width);
+            if line_length == 0 {
+                return Ok(None);
+            }
+
+            // find the first long enough run that starts within the line
+            let mut index = 0;
+            while index < line_length {
+                if state.carry[index] != fill {
+                    index += 1;
+                    continue;
+                }
+                self.fill_carry(state, index + min_run)?;
+                let run_length = state.carry[index..]
+                    .iter()
+                    .take(min_run)
+                    .take_while(|&&byte| byte == fill)
+                    .count();
+                if run_length == min_run {
+                    break;
+                }
+                index += run_length;
+            }
+
+            if index > 0 {
+                line_length = line_length.min(index);
+                let chunk_offset = state.chunk_offset;
+                let line_bytes = state.carry.drain(..line_length).collect::<Vec<_>>();
+                state.lines_read += 1;
+                consume_bytes(state, &line_bytes)?;
+                return Ok(Some((chunk_offset, line_bytes)));
+            }
+
+            let run_offset = state.chunk_offset;
+            let mut run_length = 0;
+            loop {
+                let length = state.carry.iter().take_while(|&&byte| byte == fill).count();
+                let run_bytes = state.carry.drain(..length).collect::<Vec<_>>();
+                consume_bytes(state, &run_bytes)?;
+                run_length += length as u64;
+
+                if !state.carry.is_empty() {
+                    break;
+                }
+                self.fill_carry(state, self.line_width)?;
+                if state.carry.is_empty() {
+                    break;
+                }
+            }
+            state.fill_runs.push_back((run_offset, run_length));
+        }
+    }
+
+    /// Read from the input until [`ReadState::carry`] holds at least `length` bytes, the byte limit is reached or the
+    /// input ends
+    fn fill_carry(&mut self, state: &mut ReadState, length: usize) -> io::Result<()> {
+        let Some(missing) = length
+            .checked_sub(state.carry.len())
+            .filter(|&missing| missing > 0)
+        else {
+            return Ok(());
+        };
+        let read_length = match state.remaining {
+            Some(remaining) => remaining.min(missing as u64) as usize,
+            None => missing,
+        };
+
+        let mut buf = vec![0u8; read_length];
+        let limit = state.remaining;
+        let read_length = self.read_line_bytes(state, &mut buf, limit)?;
+        state.carry.extend_from_slice(&buf[..read_length]);
+        if let Some(remaining) = state.remaining.as_mut() {
+            *remaining -= read_length as u64;
+        }
+        Ok(())
+    }
+
+    /// Format the note replacing the next collapsed run of the fill byte, if it comes before `chunk_offset`
+    fn next_fill_note(&self, state: &mut ReadState, chunk_offset: Option<u64>) -> Option<String> {
+        let &(run_offset, run_length) = state.fill_runs.front()?;
+        if chunk_offset.is_some_and(|chunk_offset| chunk_offset < run_offset) {
+            return None;
+        }
+        state.fill_runs.pop_front();
+
+        let (fill, _) = self.collapse_fill?;
+        Some(format!("[{run_length:#x} bytes of {fill:#04x} skipped]"))
+    }
+
+    /// Read and format the next line of the dump, returning `None` once the input or a limit is exhausted
+    fn next_line(&mut self, state: &mut ReadState) -> io::Result<Option<String>> {
+        let line = match state.spaced_line.take() {
+            Some(line) => line,
+            None => {
+                let Some(line) = self.next_body_line(state)? else {
+                    return Ok(None);
+                };
+                let block_spacing = self.block_spacing.filter(|_| self.renderer.is_none());
+                if block_spacing.is_some_and(|every| {
+                    state.body_lines > 0 && state.body_lines.is_multiple_of(every)
+                }) {
+                    state.spaced_line = Some(line);
+                    return Ok(Some(String::new()));
+                }
+                line
+            }
+        };
+
+        state.body_lines += 1;
+        Ok(Some(line))
+    }
+
+    /// Return the next line of the body, which is a line of the dump or a line standing for the bytes left out
+    fn next_body_line(&mut self, state: &mut ReadState) -> io::Result<Option<String>> {
+        if let Some(line) = state.pending_line.take() {
+            return Ok(Some(line));
+        }
+
+        loop {
+            let Some((chunk_offset, line_bytes)) = self.read_next_line(state)? else {
+                if let Some((offset, bytes)) = state.squeezed_line.take() {
+                    // the last line of the run is printed, so it isn't counted as left out
+                    let line = self.format_line(state, offset, &bytes)?;
+                    let lines_left_out = mem::take(&mut state.squeezed_lines) - 1;
+                    if lines_left_out > 0 && !state.squeeze_marker_printed {
+                        state.pending_line = Some(line);
+                        return Ok(Some(self.squeeze_marker(lines_left_out)));
+                    }
+                    return Ok(Some(line));
+                }
+                return Ok(self.next_fill_note(state, None));
+            };
+
+            if state
+                .fill_runs
+                .front()
+                .is_some_and(|&(offset, _)| offset < chunk_offset)
+            {
+                // the line is put back to be printed after the note, which ends any squeezed run like a different
+                // line would
+                state.lookahead.push_front((chunk_offset, line_bytes));
+                state.previous_bytes = None;
+                if let Some(marker) = self.end_squeezed_run(state) {
+                    return Ok(Some(marker));
+                }
+                return Ok(self.next_fill_note(state, Some(chunk_offset)));
+            }
+
+            if let Some(needle) = &self.grep {
+                if !contains(&line_bytes, needle) {
+                    continue;
+                }
+            }
+
+            let squeeze = self.squeeze && self.renderer.is_none();
+            if squeeze && state.previous_bytes.as_ref() == Some(&line_bytes) {
+                let run_continues = state.squeezed_line.is_some();
+                state.squeezed_line = Some((chunk_offset, line_bytes));
+                state.squeezed_lines += 1;
+                if run_continues && !state.squeeze_marker_printed && !self.squeeze_count {
+                    state.squeeze_marker_printed = true;
+                    return Ok(Some("*".to_string()));
+                }
+                continue;
+            }
+
+            let line = self.format_line(state, chunk_offset, &line_bytes)?;
+            state.previous_bytes = Some(line_bytes);
+
+            if let Some(marker) = self.end_squeezed_run(state) {
+                state.pending_line = Some(line);
+                return Ok(Some(marker));
+            }
+
+            return Ok(Some(line));
+        }
+    }
+
+    /// End the current squeezed run, if any, returning the `*` line to be printed for it if it hasn't been already
+    fn end_squeezed_run(&self, state: &mut ReadState) -> Option<String> {
+        let lines_squeezed = state.squeezed_line.take().is_some() && !state.squeeze_marker_printed;
+        let lines_left_out = mem::take(&mut state.squeezed_lines);
+        state.squeeze_marker_printed = false;
+        lines_squeezed.then(|| self.squeeze_marker(lines_left_out))
+    }
+
+    /// The line printed in place of `lines_left_out` identical lines
+    fn squeeze_marker(&self, lines_left_out: u64) -> String {
+        match self.squeeze_count {
+            true => {
+                let bytes = lines_left_out * self.line_width as u64;
+                format!("*  ({bytes:#x} bytes identical)")
+            }
+            false => "*".to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    fn format_contents(&mut self) -> io::Result<Vec<String>> {
+        let mut state = self.begin(false)?;
+        let mut lines = Vec::new();
+        while let Some(line) = self.next_line(&mut state)? {
+            lines.push(line);
+        }
+
+        Ok(lines)
+    }
+
+    /// Return an iterator yielding the formatted lines of the dump (excluding the header) one at a time
+    ///
+    /// Lines are read and formatted lazily, so the whole dump is never held in memory at once.
+    pub fn lines(self) -> impl Iterator<Item = io::Result<String>> {
+        Lines {
+            dumper: self,
+            state: None,
+            finished: false,
+        }
+    }
+
+    fn write_dump<W: Write>(
+        &mut self,
+        writer: &mut W,
+        is_terminal: bool,
+        trailing_newline: bool,
+    ) -> io::Result<()> {
+        let mut state = self.begin(is_terminal)?;
+        // without a delimiter every line is complete once it is written, so it is terminated right away for the
+        // output to be readable while the dump is still being written (as when following a file)
+        let delimiter = self.renderer().line_delimiter().to_string();
+        let mut output = TerminatedLines {
+            writer,
+            terminator: self.line_terminator.as_str(),
+            terminate_each: trailing_newline && delimiter.is_empty(),
+            started: false,
+        };
+
+        if let Some(header) = self.renderer().header() {
+            for line in header.lines() {
+                output.write_line(line)?;
+            }
+        }
+
+        // the delimiter only goes between lines, so each line is held back until the next one has been read, unless
+        // there is no delimiter and the line can be written as soon as it is read
+        let mut previous_line = None;
+        while let Some(line) = self.next_line(&mut state)? {
+            if delimiter.is_empty() {
+                output.write_line(&line)?;
+            } else if let Some(previous_line) = previous_line.replace(line) {
+                output.write_line(&format!("{previous_line}{delimiter}"))?;
+            }
+            if mem::take(&mut state.input_waiting) {
+                output.writer.flush()?;
+            }
+        }
+        if let Some(last_line) = previous_line {
+            output.write_line(&last_line)?;
+        }
+
+        if self.summary && self.renderer.is_none() {
+            let bytes_read = state.bytes_read;
+            let crc32 = state.crc32.finish();
+            output.write_line(&format!("-- {bytes_read} bytes, crc32={crc32:08x}"))?;
+        }
+
+        if let Some(byte_counts) = state
+            .byte_counts
+            .as_ref()
+            .filter(|_| self.renderer.is_none())
+        {
+            for line in format_histogram(byte_counts, self.classic.uppercase) {
+                output.write_line(&line)?;
+            }
+        }
+
+        if let Some(footer) = self.renderer().footer(state.bytes_dumped) {
+            for line in footer.lines() {
+                output.write_line(line)?;
+            }
+        }
+
+        if trailing_newline && output.started && !output.terminate_each {
+            output.writer.write_all(output.terminator.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Write the formatted dump to `writer` taking into account the selected options
+    ///
+    /// The last line ends with the line terminator unless [`Dumper::trailing_newline`] is disabled.
+    ///
+    /// [`ColorChoice::Auto`] never colors the output here, since `writer` isn't known to be a terminal.
+    pub fn dump_to<W: Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.write_dump(writer, false, self.trailing_newline)
+    }
+
+    /// Return the formatted dump as a string, with lines separated by the [`Dumper::line_terminator`] and none after
+    /// the last line
+    pub fn render(&mut self) -> io::Result<String> {
+        let mut output = Vec::new();
+        self.write_dump(&mut output, false, false)?;
+
+        Ok(String::from_utf8(output).expect("dump output should be valid UTF-8"))
+    }
+
+    /// The position and width of each column of a full line of the default layout, in characters
+    ///
+    /// The widths of the offset and line number columns are fitted to the input once dumping begins, as is the line
+    /// width if [`Dumper::line_width_auto`] is set, so the layout matches the last dump rendered. Before the first
+    /// one it assumes the default widths.
+    pub fn layout(&self) -> Layout {
+        self.classic.layout()
+    }
+
+    /// Print the formatted dump taking into account the selected options
+    pub fn dump(&mut self) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        let is_terminal = stdout.is_terminal();
+        self.write_dump(&mut stdout, is_terminal, self.trailing_newline)
+    }
+
+    /// Turn this dumper into a [`WritingDumper`] that formats the bytes written to it rather than the bytes read
+    /// from its reader, writing the dump to `output`
+    pub fn into_writer<W: Write>(self, output: W) -> WritingDumper<W> {
+        let mut classic = self.classic;
+        classic.color = self.color_choice.enabled(false);
+        if self.line_width_auto {
+            classic.line_width = 0x10;
+        }
+        let line_width = classic.line_width;
+        WritingDumper::from_parts(
+            output,
+            classic,
+            self.renderer,
+            line_width,
+            self.base_address,
+        )
+    }
+
+    /// Turn this dumper into a [`StreamDumper`] that formats the chunks of bytes fed to it rather than the bytes read
+    /// from its reader
+    pub fn into_stream(self) -> StreamDumper {
+        StreamDumper::from_writer(self.into_writer(Vec::new()))
+    }
+}
+
+impl<R> Dumper<R>
+where
+    R: Read + Seek,
+{
+    /// Construct a new instance of [`Dumper`] for a seekable reader
+    ///
+    /// The remaining length of the input is measured if its end can be seeked to, so that the offset column can be
+    /// widened and [`LineRenderer::begin`] is passed the number of bytes to be dumped, and [`Dumper::skip`] seeks
+    /// instead of reading the skipped bytes.
+    pub fn from_seekable(reader: R) -> Dumper<R> {
+        Dumper::new(reader).into_seekable()
+    }
+
+    /// Measure the input and skip bytes by seeking, as [`Dumper::from_seekable`] does
+    pub(crate) fn into_seekable(mut self) -> Dumper<R> {
+        self.input_length = remaining_length;
+        self.skip_input = seek_input;
+        self
+    }
+
+    /// Set the offset of the byte in the input buffer to start dumping at
+    pub fn start_offset(mut self, start_offset: u64) -> Dumper<R> {
+        self.reader
+            .seek(SeekFrom::Start(start_offset))
+            .unwrap_or_else(|err| {
+                eprintln!("error: couldn't seek to offset {start_offset}: {err}");
+                process::exit(1);
+            });
+        self
+    }
+}
+
+/// Fail with [`DumperError::OffsetTooWide`] if `offset` doesn't fit in the offset column of `classic`
+/// The error returned once the offset of a byte to be dumped doesn't fit in 64 bits
+fn offset_overflow() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, DumperError::OffsetOverflow)
+}
+
+/// Offset of the last byte of a dump of `length` bytes starting at `first_offset`, or of the first byte if the length
+/// isn't known, checking that the offset just past the end fits in 64 bits
+fn last_offset(first_offset: u64, length: Option<u64>) -> io::Result<u64> {
+    let length = length.unwrap_or_default();
+    first_offset
+        .checked_add(length)
+        .map(|end| end - length.min(1))
+        .ok_or_else(offset_overflow)
+}
+
+pub(crate) fn check_offset_fits(classic: &ClassicRenderer, offset: u64) -> io::Result<()> {
+    match classic.offset_fits(offset) {
+        true => Ok(()),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            DumperError::OffsetTooWide {
+                offset,
+                width: classic.offset_digits,
+            },
+        )),
+    }
+}
+
+/// Number of columns of the terminal standard output is connected to, falling back to the `COLUMNS` environment
+/// variable
+fn terminal_columns() -> Option<usize> {
+    match terminal_size::terminal_size() {
+        Some((terminal_size::Width(columns), _)) => Some(columns as usize),
+        None => std::env::var("COLUMNS").ok()?.parse().ok(),
+    }
+}
+
+impl<'a> Dumper<Cursor<&'a [u8]>> {
+    /// Construct a new instance of [`Dumper`] for bytes in memory
+    pub fn from_bytes(bytes: &'a [u8]) -> Dumper<Cursor<&'a [u8]>> {
+        Dumper::from_seekable(Cursor::new(bytes))
+    }
+
+    /// Return the formatted dump as a string like [`Dumper::render`], formatting the lines on several threads
+    ///
+    /// Every line only depends on its own bytes and offset, so the bytes are split into chunks of whole lines which
+    /// are formatted in parallel and then joined in order. Options carrying state from one line to the next
+    /// ([`Dumper::squeeze`], [`Dumper::grep`], [`Dumper::highlight`], [`Dumper::collapse_fill`], [`Dumper::summary`]
+    /// and [`Dumper::histogram`]), sampling with [`Dumper::stride`], [`Dumper::block_spacing`], annotations and
+    /// custom renderers aren't supported, and the dump is then rendered on the current thread instead.
+    #[cfg(feature = "rayon")]
+    pub fn render_parallel(&mut self) -> io::Result<String> {
+        use rayon::prelude::*;
+
+        if self.renderer.is_some()
+            || self.squeeze
+            || self.grep.is_some()
+            || self.highlight.is_some()
+            || !self.annotations.is_empty()
+            || self.collapse_fill.is_some()
+            || self.stride > 1
+            || self.block_spacing.is_some()
+            || self.summary
+            || self.histogram
+        {
+            return self.render();
+        }
+
+        let state = self.begin(false)?;
+        let input = *self.reader.get_ref();
+        let start = (self.reader.position() as usize).min(input.len());
+        let mut length = input.len() - start;
+        if let Some(byte_count) = self.byte_count {
+            length = length.min(byte_count.try_into().unwrap_or(usize::MAX));
+        }
+        if let Some(line_count) = self.line_count {
+            length = length.min(line_count.saturating_mul(self.line_width));
+        }
+        let bytes = &input[start..start + length];
+        self.reader.set_position((start + length) as u64);
+
+        let terminator = self.line_terminator.as_str();
+        let line_width = self.line_width;
+        let chunk_lines = PARALLEL_CHUNK_LENGTH.div_ceil(line_width);
+        let classic = &self.classic;
+        let body: Vec<String> = bytes
+            .par_chunks(chunk_lines * line_width)
+            .enumerate()
+            .map(|(chunk, chunk_bytes)| {
+                let mut lines = String::new();
+                for (index, line_bytes) in chunk_bytes.chunks(line_width).enumerate() {
+                    let line_index = chunk * chunk_lines + index;
+                    if index > 0 {
+                        lines.push_str(terminator);
+                    }
+                    let offset = state.first_offset + (line_index * line_width) as u64;
+                    classic.write_numbered_line(
+                        &mut lines,
+                        line_index as u64 + 1,
+                        offset,
+                        line_bytes,
+                    );
+                }
+                lines
+            })
+            .collect();
+
+        // the chunks are dropped as soon as they are copied, since the dump of a large input takes up a lot of memory
+        let header = classic.header();
+        let footer = classic.footer(length as u64);
+        let mut output =
+            String::with_capacity(body.iter().map(String::len).sum::<usize>() + 0x1000);
+        let mut lines = header
+            .iter()
+            .flat_map(|header| header.lines().map(String::from))
+            .chain(body)
+            .chain(
+                footer
+                    .iter()
+                    .flat_map(|footer| footer.lines().map(String::from)),
+            );
+        if let Some(first_line) = lines.next() {
+            output.push_str(&first_line);
+        }
+        for line in lines {
+            output.push_str(terminator);
+            output.push_str(&line);
+        }
+        Ok(output)
+    }
+
+    /// Print the formatted dump like [`Dumper::dump`], formatting the lines on several threads like
+    /// [`Dumper::render_parallel`]
+    ///
+    /// Colors and the automatic line width depend on whether standard output is a terminal, so the dump is only
+    /// formatted in parallel when it isn't, e.g. when it is piped into a file.
+    #[cfg(feature = "rayon")]
+    pub fn dump_parallel(&mut self) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        if stdout.is_terminal() {
+            return self.write_dump(&mut stdout, true, self.trailing_newline);
+        }
+
+        let output = self.render_parallel()?;
+        stdout.write_all(output.as_bytes())?;
+        if self.trailing_newline && !output.is_empty() {
+            stdout.write_all(self.line_terminator.as_str().as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a [u8]> for Dumper<Cursor<&'a [u8]>> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Dumper::from_bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for Dumper<Cursor<Vec<u8>>> {
+    fn from(bytes: Vec<u8>) -> Self {
+        Dumper::from_seekable(Cursor::new(bytes))
+    }
+}
+
+/// Skip `bytes` bytes of `reader` by reading and discarding them
+fn discard_input<R: Read>(reader: &mut R, bytes: u64) -> io::Result<()> {
+    let mut reader = reader.take(bytes);
+    loop {
+        match io::copy(&mut reader, &mut io::sink()) {
+            Ok(_) => return Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Skip `bytes` bytes of `reader` by seeking past them
+fn seek_input<R: Seek>(reader: &mut R, bytes: u64) -> io::Result<()> {
+    let bytes = i64::try_from(bytes)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "skip is too large"))?;
+    reader.seek(SeekFrom::Current(bytes))?;
+    Ok(())
+}
+
+/// The length of an input that can't be measured without reading it
+fn unknown_length<R: Read>(_reader: &mut R) -> io::Result<Option<u64>> {
+    Ok(None)
+}
+
+/// Number of bytes between the current position of `reader` and the end of the input, or `None` if the end can't be
+/// seeked to, as with the memory files of processes
+fn remaining_length<R: Seek>(reader: &mut R) -> io::Result<Option<u64>> {
+    let position = reader.stream_position()?;
+    let Ok(end) = reader.seek(SeekFrom::End(0)) else {
+        return Ok(None);
+    };
+    reader.seek(SeekFrom::Start(position))?;
+    Ok(Some(end.saturating_sub(position)))
+}
+
+/// Number of bytes formatted together on one thread by [`Dumper::render_parallel`]
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_LENGTH: usize = 0x40000;
+
+/// Number of bytes read from the input at a time, independently of the line width
+const READ_BUFFER_LENGTH: usize = 0x10000;
+
+/// Count `bytes` as read from the input, advancing the offset of the next line past them
+fn consume_bytes(state: &mut ReadState, bytes: &[u8]) -> io::Result<()> {
+    state.chunk_offset = state
+        .chunk_offset
+        .checked_add(bytes.len() as u64)
+        .ok_or_else(offset_overflow)?;
+    state.bytes_read += bytes.len() as u64;
+    state.crc32.update(bytes);
+    if let Some(byte_counts) = state.byte_counts.as_mut() {
+        for &byte in bytes {
+            byte_counts[byte as usize] += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Number of byte values listed in the histogram
+const HISTOGRAM_LENGTH: usize = 16;
+
+/// Length of the bar of the most common byte value in the histogram
+const HISTOGRAM_BAR_LENGTH: usize = 32;
+
+/// Format the most common byte values in `byte_counts` as histogram lines, e.g. `-- 20     12  25.0% ####`
+fn format_histogram(byte_counts: &[u64; 256], uppercase: bool) -> Vec<String> {
+    let mut values = (0..=255u8)
+        .filter(|&byte| byte_counts[byte as usize] > 0)
+        .collect::<Vec<_>>();
+    values.sort_by_key(|&byte| std::cmp::Reverse(byte_counts[byte as usize]));
+    values.truncate(HISTOGRAM_LENGTH);
+
+    let total = byte_counts.iter().sum::<u64>();
+    let max_count = values.first().map_or(0, |&byte| byte_counts[byte as usize]);
+    let count_width = max_count.to_string().len();
+    values
+        .into_iter()
+        .map(|byte| {
+            let count = byte_counts[byte as usize];
+            let percentage = count as f64 * 100.0 / total as f64;
+            let bar_length = (count * HISTOGRAM_BAR_LENGTH as u64).div_ceil(max_count) as usize;
+            let byte = match uppercase {
+                true => format!("{byte:02X}"),
+                false => format!("{byte:02x}"),
+            };
+            format!(
+                "-- {byte} {count:>count_width$} {percentage:>5.1}% {}",
+                "#".repeat(bar_length)
+            )
+        })
+        .collect()
+}
+
+/// Whether `needle` occurs anywhere in `haystack`
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty()
+        || haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+    use crate::{
+        max_line_width_for, Base64Renderer, CIncludeRenderer, Color, LayoutOptions, PlainRenderer,
+        PythonBytesRenderer, RustArrayRenderer,
+    };
+
+    #[test]
+    fn lorem() {
+        let expected =
+            "00000000 | 4c 6f 72 65 6d 20 69 70 73 75 6d 20 64 6f 6c 6f | Lorem ipsum dolo\n\
+             00000010 | 72 20 73 69 74 20 61 6d 65 74 20 63 6f 6e 73 65 | r sit amet conse\n\
+             00000020 | 63 74 65 74 75 72 20 61 64 69 70 69 73 69 63 69 | ctetur adipisici\n\
+             00000030 | 6e 67 20 65 6c 69 74 2e 20 41 74 71 75 65 20 6f | ng elit. Atque o\n\
+             00000040 | 6d 6e 69 73 20 64 69 67 6e 69 73 73 69 6d 6f 73 | mnis dignissimos\n\
+             00000050 | 20 74 6f 74 61 6d 20 63 6f 6e 73 65 71 75 75 6e |  totam consequun\n\
+             00000060 | 74 75 72 20 61 6c 69 71 75 69 64 20 6d 69 6e 69 | tur aliquid mini\n\
+             00000070 | 6d 61 20 6e 61 74 75 73 20 64 6f 6c 6f 72 75 6d | ma natus dolorum\n\
+             00000080 | 20 73 65 64 20 69 70 73 75 6d 20 69 6c 6c 75 6d |  sed ipsum illum\n\
+             00000090 | 3f                                              | ?";
+
+        let lorem = "Lorem ipsum dolor sit amet consectetur adipisicing elit. Atque omnis dignissimos totam consequuntur aliquid minima natus dolorum sed ipsum illum?";
+        let mut reader = Cursor::new(lorem.as_bytes().to_vec());
+
+        let result = Dumper::new(&mut reader)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn with_control_codes() {
+        let expected =
+            "00000000 | 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f | ␀␁␂␃␄␅␆␇␈␉␊␋␌␍␎␏\n\
+             00000010 | 10 11 12 13 14 15 16 17 18 19 1a 1b 1c 1d 1e 1f | ␐␑␒␓␔␕␖␗␘␙␚␛␜␝␞␟\n\
+             00000020 | 20 21 22 23 24 25 26 27 28 29 2a 2b 2c 2d 2e 2f |  !\"#$%&'()*+,-./\n\
+             00000030 | 30 31 32 33 34 35 36 37 38 39 3a 3b 3c 3d 3e 3f | 0123456789:;<=>?\n\
+             00000040 | 40 41 42 43 44 45 46 47 48 49 4a 4b 4c 4d 4e 4f | @ABCDEFGHIJKLMNO\n\
+             00000050 | 50 51 52 53 54 55 56 57 58 59 5a 5b 5c 5d 5e 5f | PQRSTUVWXYZ[\\]^_\n\
+             00000060 | 60 61 62 63 64 65 66 67 68 69 6a 6b 6c 6d 6e 6f | `abcdefghijklmno\n\
+             00000070 | 70 71 72 73 74 75 76 77 78 79 7a 7b 7c 7d 7e 7f | pqrstuvwxyz{|}~␡\n\
+             00000080 | 80 81 82 83 84 85 86 87 88 89 8a 8b 8c 8d 8e 8f | ................\n\
+             00000090 | 90 91 92 93 94 95 96 97 98 99 9a 9b 9c 9d 9e 9f | ................\n\
+             000000a0 | a0 a1 a2 a3 a4 a5 a6 a7 a8 a9 aa ab ac ad ae af | ................\n\
+             000000b0 | b0 b1 b2 b3 b4 b5 b6 b7 b8 b9 ba bb bc bd be bf | ................\n\
+             000000c0 | c0 c1 c2 c3 c4 c5 c6 c7 c8 c9 ca cb cc cd ce cf | ................\n\
+             000000d0 | d0 d1 d2 d3 d4 d5 d6 d7 d8 d9 da db dc dd de df | ................\n\
+             000000e0 | e0 e1 e2 e3 e4 e5 e6 e7 e8 e9 ea eb ec ed ee ef | ................\n\
+             000000f0 | f0 f1 f2 f3 f4 f5 f6 f7 f8 f9 fa fb fc fd fe ff | ................";
+
+        let all_bytes = (0..=255).collect::<Vec<_>>();
+        let mut reader = Cursor::new(all_bytes);
+        let result = Dumper::new(&mut reader)
+            .control_pictures(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn with_line_count() {
+        let expected =
+            "00000000 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000010 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000020 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000030 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000040 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000050 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000060 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000070 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000080 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000090 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................";
+
+        let bytes = vec![0xff; 100 * 0x10];
+        let mut reader = Cursor::new(bytes);
+        let result = Dumper::new(&mut reader)
+            .line_count(Some(10))
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_unbounded_line_count() {
+        let bytes: Vec<u8> = (0..=0xff).collect();
+        let expected = Dumper::new(Cursor::new(bytes.clone())).render().unwrap();
+        let result = Dumper::new(Cursor::new(bytes))
+            .line_count(Some(usize::MAX))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn offsets_past_the_largest_offset() {
+        let result = Dumper::new(&[0u8; 20][..])
+            .base_address(u64::MAX - 4)
+            .render();
+        assert!(result.is_err());
+
+        let result = Dumper::from_seekable(Cursor::new([0u8; 20]))
+            .base_address(u64::MAX - 4)
+            .render();
+        assert!(result.is_err());
+
+        let result = Dumper::from_seekable(Cursor::new([0u8; 5]))
+            .base_address(u64::MAX - 5)
+            .render();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn with_line_width() {
+        let expected = "00000000 | ff ff ff ff | ....\n\
+                        00000004 | ff ff ff ff | ....\n\
+                        00000008 | ff ff ff ff | ....\n\
+                        0000000c | ff ff       | ..";
+
+        let bytes = vec![0xff; 3 * 0x4 + 2];
+        let mut reader = Cursor::new(bytes);
+        let result = Dumper::new(&mut reader)
+            .line_width(4)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_byte_group() {
+        let expected = "00000000 | ffffffff ffffffff ffffffff ffffffff | ................\n\
+                        00000010 | ffffffff ffffffff ffffffff ffffffff | ................\n\
+                        00000020 | ffffffff ffffffff ffffffff ffffffff | ................";
+
+        let bytes = vec![0xff; 3 * 0x10];
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .byte_group_length(4)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn dump_to_writer() {
+        let expected = "         | 00 01 02 03 |     \n\
+                        ---------+-------------+-----\n\
+                        00000000 | 48 65 6c 6c | Hell\n\
+                        00000004 | 6f          | o\n";
+
+        let reader = Cursor::new(b"Hello".to_vec());
+        let mut output = Vec::new();
+        Dumper::new(reader)
+            .line_width(4)
+            .dump_to(&mut output)
+            .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn read_error() {
+        struct BrokenReader;
+
+        impl Read for BrokenReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+            }
+        }
+
+        impl Seek for BrokenReader {
+            fn seek(&mut self, _pos: SeekFrom) -> io::Result<u64> {
+                Ok(0)
+            }
+        }
+
+        let mut output = Vec::new();
+        let result = Dumper::new(BrokenReader).dump_to(&mut output);
+
+        assert_eq!(io::ErrorKind::BrokenPipe, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn interrupted_reads() {
+        struct InterruptedReader {
+            inner: Cursor<Vec<u8>>,
+            interrupted: bool,
+        }
+
+        impl Read for InterruptedReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if !self.interrupted {
+                    self.interrupted = true;
+                    return Err(io::Error::from(io::ErrorKind::Interrupted));
+                }
+                self.inner.read(buf)
+            }
+        }
+
+        let bytes = b"Lorem ipsum dolor sit amet".to_vec();
+        let expected = Dumper::new(Cursor::new(bytes.clone())).render().unwrap();
+
+        let reader = InterruptedReader {
+            inner: Cursor::new(bytes),
+            interrupted: false,
+        };
+        let result = Dumper::new(reader).render().unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn waiting_reads() {
+        /// Returns the chunks one at a time, and `WouldBlock` after each of them
+        struct WaitingReader {
+            chunks: VecDeque<&'static [u8]>,
+            waiting: bool,
+        }
+
+        impl Read for WaitingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if mem::replace(&mut self.waiting, false) {
+                    return Err(io::Error::from(io::ErrorKind::WouldBlock));
+                }
+                let Some(chunk) = self.chunks.pop_front() else {
+                    return Ok(0);
+                };
+                self.waiting = true;
+                buf[..chunk.len()].copy_from_slice(chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let expected =
+            "00000000 | 4c 6f 72 65 6d 20 69 70 73 75 6d 20 64 6f 6c 6f | Lorem ipsum dolo\n\
+                        00000010 | 72 20 73 69                                     | r si\n\
+                        00000014 | 74 20 61 6d 65 74                               | t amet\n\
+                        0000001a | 2c 20 63 6f 6e 73                               | , cons\n\
+                        00000020 | 65 63                                           | ec\n";
+
+        let reader = WaitingReader {
+            chunks: VecDeque::from([&b"Lorem ipsum dolor si"[..], b"t amet", b", consec"]),
+            waiting: false,
+        };
+        let mut output = Vec::new();
+        Dumper::new(reader)
+            .header(false)
+            .dump_to(&mut output)
+            .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn short_reads() {
+        struct OneByteReader(Cursor<Vec<u8>>);
+
+        impl Read for OneByteReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let length = buf.len().min(1);
+                self.0.read(&mut buf[..length])
+            }
+        }
+
+        impl Seek for OneByteReader {
+            fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+                self.0.seek(pos)
+            }
+        }
+
+        let expected =
+            "00000000 | 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f | ................\n\
+             00000010 | 10 11 12 13 14 15 16 17 18 19 1a 1b 1c 1d 1e 1f | ................\n\
+             00000020 | 20 21 22                                        |  !\"";
+
+        let reader = OneByteReader(Cursor::new((0..0x23).collect()));
+        let result = Dumper::new(reader).format_contents().unwrap().join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_skip() {
+        let expected = "00000006 | 69 70 73 75 | ipsu\n\
+                        0000000a | 6d 20 64 6f | m do\n\
+                        0000000e | 6c 6f 72    | lor";
+
+        let reader = Cursor::new(b"Lorem ipsum dolor".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .skip(6)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_byte_count() {
+        let expected =
+            "00000000 | ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff ff | ................\n\
+             00000010 | ff ff ff ff                                     | ....";
+
+        let bytes = vec![0xff; 3 * 0x10];
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .byte_count(Some(20))
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_uppercase() {
+        let expected = "000000A0 | AB CD EF 4C | ...L";
+
+        let mut bytes = vec![0x00; 0xa0];
+        bytes.extend([0xab, 0xcd, 0xef, 0x4c]);
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .skip(0xa0)
+            .uppercase(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn render_to_string() {
+        let expected = "         | 00 01 02 03 |     \n\
+                        ---------+-------------+-----\n\
+                        00000000 | 48 65 6c 6c | Hell\n\
+                        00000004 | 6f          | o";
+
+        let reader = Cursor::new(b"Hello".to_vec());
+        let result = Dumper::new(reader).line_width(4).render().unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn lines_iterator() {
+        let expected = vec![
+            "00000002 | 72 65 6d 20 | rem ",
+            "00000006 | 69 70 73 75 | ipsu",
+            "0000000a | 6d          | m",
+        ];
+
+        let reader = Cursor::new(b"Lorem ipsum dolor".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .skip(2)
+            .byte_count(Some(9))
+            .lines()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn invalid_widths() {
+        let result = Dumper::new(Cursor::new(Vec::new())).try_line_width(0);
+        assert!(matches!(result, Err(DumperError::InvalidLineWidth(0))));
+
+        let result = Dumper::new(Cursor::new(Vec::new())).try_byte_group_length(257);
+        assert!(matches!(
+            result,
+            Err(DumperError::InvalidByteGroupLength(257))
+        ));
+
+        let result = Dumper::new(Cursor::new(Vec::new()))
+            .byte_group_length(2)
+            .try_decode_as(Some(IntWidth::U32));
+        assert!(matches!(
+            result,
+            Err(DumperError::IntWidthMismatch {
+                int_width: IntWidth::U32,
+                byte_group_length: 2,
+            })
+        ));
+
+        let result = Dumper::new(Cursor::new(Vec::new())).try_placeholder('\u{4e00}');
+        assert!(matches!(
+            result,
+            Err(DumperError::InvalidPlaceholder('\u{4e00}'))
+        ));
+    }
+
+    #[test]
+    fn with_color() {
+        let expected = "00000000 | \x1b[90m00\x1b[0m\x1b[36m41\x1b[0m \x1b[32m0a\x1b[0m   \
+                        | \x1b[90m.\x1b[0m\x1b[36mA\x1b[0m\x1b[32m.\x1b[0m";
+
+        let reader = Cursor::new(vec![0x00, 0x41, 0x0a]);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .byte_group_length(2)
+            .color(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn auto_color_without_terminal() {
+        let reader = Cursor::new(vec![0x00, 0x41, 0x0a]);
+        let mut output = Vec::new();
+        Dumper::new(reader)
+            .color_choice(ColorChoice::Auto)
+            .dump_to(&mut output)
+            .unwrap();
+
+        assert!(!output.contains(&0x1b));
+    }
+
+    #[test]
+    fn with_offset_base() {
+        let expected = "            | 00 01 02 03 |     \n\
+                        ------------+-------------+-----\n\
+                        00000000000 | 48 65 6c 6c | Hell\n\
+                        00000000004 | 6f          | o";
+
+        let reader = Cursor::new(b"Hello".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .offset_base(OffsetBase::Octal)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_base_address() {
+        let expected = "08000000 | 48 65 6c 6c | Hell\n\
+                        08000004 | 6f          | o";
+
+        let reader = Cursor::new(b"Hello".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .base_address(0x0800_0000)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_wide_base_address() {
+        let expected = "0000000100000000 | 6c 6c       | ll";
+
+        let reader = Cursor::new(b"Hell".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .base_address(0xffff_fffe)
+            .skip(2)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_large_input() {
+        // 6 GiB of zeros, without actually allocating them
+        struct LargeReader {
+            position: u64,
+        }
+
+        impl LargeReader {
+            const LENGTH: u64 = 6 << 30;
+        }
+
+        impl Read for LargeReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let length = (Self::LENGTH.saturating_sub(self.position)).min(buf.len() as u64);
+                buf[..length as usize].fill(0);
+                self.position += length;
+                Ok(length as usize)
+            }
+        }
+
+        impl Seek for LargeReader {
+            fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+                self.position = match pos {
+                    SeekFrom::Start(offset) => offset,
+                    SeekFrom::End(offset) => Self::LENGTH.saturating_add_signed(offset),
+                    SeekFrom::Current(offset) => self.position.saturating_add_signed(offset),
+                };
+                Ok(self.position)
+            }
+        }
+
+        let expected = "                 | 00 01 02 03 |     \n\
+                        -----------------+-------------+-----\n\
+                        0000000140000000 | 00 00 00 00 | ....";
+
+        let reader = LargeReader { position: 0 };
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .skip(5 << 30)
+            .line_count(Some(1))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn squeeze_is_opt_in() {
+        let bytes = vec![0xaa; 0x10];
+        let lines =
+            |dumper: Dumper<Cursor<Vec<u8>>>| dumper.line_width(4).format_contents().unwrap();
+
+        let default = lines(Dumper::new(Cursor::new(bytes.clone())));
+        assert_eq!(4, default.len());
+        assert!(!default.contains(&"*".to_string()));
+        assert_eq!(
+            default,
+            lines(Dumper::new(Cursor::new(bytes.clone())).squeeze(false))
+        );
+
+        let squeezed = lines(Dumper::new(Cursor::new(bytes)).squeeze(true));
+        assert_eq!(
+            vec![
+                "00000000 | aa aa aa aa | ....",
+                "*",
+                "0000000c | aa aa aa aa | ...."
+            ],
+            squeezed
+        );
+    }
+
+    #[test]
+    fn with_squeeze() {
+        let expected = "00000000 | 00 00 00 00 | ....\n\
+                        *\n\
+                        0000000c | 41 41 41 41 | AAAA\n\
+                        *\n\
+                        0000001c | 00 00 00 00 | ....\n\
+                        00000020 | 00 00 00 00 | ....";
+
+        let mut bytes = vec![0x00; 12];
+        bytes.extend([0x41; 16]);
+        bytes.extend([0x00; 8]);
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .squeeze(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+
+        let expected = "00000000 | 00 00 00 00 | ....\n\
+                        *\n\
+                        00000008 | 41 41 41 41 | AAAA";
+
+        let mut bytes = vec![0x00; 8];
+        bytes.extend([0x41; 4]);
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .squeeze(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_squeeze_count() {
+        let expected = "00000000 | 00 00 00 00 | ....\n\
+                        *  (0x8 bytes identical)\n\
+                        0000000c | 41 41 41 41 | AAAA\n\
+                        *  (0xc bytes identical)\n\
+                        0000001c | 00 00 00 00 | ....\n\
+                        *  (0x8 bytes identical)\n\
+                        00000028 | 00 00 00 00 | ....";
+
+        let mut bytes = vec![0x00; 12];
+        bytes.extend([0x41; 16]);
+        bytes.extend([0x00; 16]);
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .squeeze(true)
+            .squeeze_count(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_block_spacing() {
+        let expected = "00000000 | 00 01 02 03 | ....\n\
+                        00000004 | 00 00 00 00 | ....\n\
+                        \n\
+                        *\n\
+                        00000010 | 04 05 06 07 | ....\n\
+                        \n\
+                        00000014 | 08 09       | ..";
+
+        let mut bytes = vec![0x00, 0x01, 0x02, 0x03];
+        bytes.extend([0x00; 12]);
+        bytes.extend([0x04, 0x05, 0x06, 0x07, 0x08, 0x09]);
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .squeeze(true)
+            .block_spacing(Some(2))
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_renderer() {
+        struct PlainRenderer;
+
+        impl LineRenderer for PlainRenderer {
+            fn render_line(&self, offset: u64, bytes: &[u8]) -> String {
+                let hex = bytes
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>();
+                format!("{offset}: {hex}")
+            }
+        }
+
+        let expected = "2: 6c6c6f\n\
+                        5: 21";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(3)
+            .skip(2)
+            .uppercase(true)
+            .with_renderer(Box::new(PlainRenderer))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn format_line_into() {
+        let renderer = ClassicRenderer::default();
+        let first = renderer.render_line(0, b"Hello");
+        let second = renderer.render_line(0x10, b"world");
+
+        let mut buf = String::new();
+        renderer.format_line_into(0, b"Hello", &mut buf);
+        assert_eq!(first, buf);
+
+        // the buffer is appended to rather than cleared
+        renderer.format_line_into(0x10, b"world", &mut buf);
+        assert_eq!(first + &second, buf);
+    }
+
+    #[test]
+    fn format_empty_line() {
+        let mut buf = String::new();
+        ClassicRenderer::new().format_line_into(0, &[], &mut buf);
+
+        assert_eq!(format!("00000000 | {:47} | ", ""), buf);
+    }
+
+    #[test]
+    fn with_plain_renderer() {
+        let expected = "4c6f72656d20697073756d20\n\
+                        646f6c6f72";
+
+        let reader = Cursor::new(b"Lorem ipsum dolor".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(12)
+            .with_renderer(Box::new(PlainRenderer::new()))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_c_include_renderer() {
+        let expected = "unsigned char hello_txt[] = {\n\
+                        \x20 0x48, 0x65, 0x6c, 0x6c,\n\
+                        \x20 0x6f, 0x21\n\
+                        };\n\
+                        unsigned int hello_txt_len = 6;";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .with_renderer(Box::new(CIncludeRenderer::new("hello.txt")))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_filtered_c_include_renderer() {
+        let expected = "unsigned char hello_txt[] = {\n\
+                        \x20 0x48, 0x65, 0x6c, 0x6c,\n\
+                        \x20 0x6f, 0x72, 0x6c, 0x64\n\
+                        };\n\
+                        unsigned int hello_txt_len = 8;";
+
+        let reader = Cursor::new(b"Hello, world!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .stride(2)
+            .with_renderer(Box::new(CIncludeRenderer::new("hello.txt")))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_rust_array_renderer() {
+        let expected = "const HELLO: [u8; 6] = [\n\
+                        \x20   0x48, 0x65, 0x6c, 0x6c,\n\
+                        \x20   0x6f, 0x21,\n\
+                        ];";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::from_seekable(reader)
+            .line_width(4)
+            .with_renderer(Box::new(RustArrayRenderer::new("HELLO")))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+
+        let expected = "const HELLO: &[u8] = &[\n\
+                        \x20   0x48, 0x65, 0x6c, 0x6c,\n\
+                        \x20   0x6f, 0x21,\n\
+                        ];";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .with_renderer(Box::new(RustArrayRenderer::new("HELLO").slice(true)))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_filtered_rust_array_renderer() {
+        let bytes = b"Hello, world! Hello, world!".to_vec();
+        let result = Dumper::new(Cursor::new(bytes.clone()))
+            .line_width(4)
+            .grep(b"o".to_vec())
+            .with_renderer(Box::new(RustArrayRenderer::new("HELLO")))
+            .render()
+            .unwrap();
+        assert!(result.starts_with("const HELLO: &[u8] = &["), "{result}");
+
+        let result = Dumper::new(Cursor::new(bytes))
+            .line_width(4)
+            .stride(2)
+            .with_renderer(Box::new(RustArrayRenderer::new("HELLO")))
+            .render()
+            .unwrap();
+        assert!(result.starts_with("const HELLO: &[u8] = &["), "{result}");
+    }
+
+    #[test]
+    fn with_base64_renderer() {
+        let expected = "cmVtIGlw\n\
+                        c3VtIGRv\n\
+                        bG8=";
+
+        let reader = Cursor::new(b"Lorem ipsum dolor".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(6)
+            .skip(2)
+            .byte_count(Some(14))
+            .with_renderer(Box::new(Base64Renderer::new()))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_python_bytes_renderer() {
+        let expected = "hello_py = (\n\
+                        \x20   b'It\\'s'\n\
+                        \x20   b' \\\\\\x00\\xff'\n\
+                        \x20   b'\\n'\n\
+                        )";
+
+        let reader = Cursor::new(b"It's \\\0\xff\n".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .with_renderer(Box::new(PythonBytesRenderer::new("hello.py")))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+
+        let result = Dumper::new(Cursor::new(Vec::new()))
+            .with_renderer(Box::new(PythonBytesRenderer::new("empty")))
+            .render()
+            .unwrap();
+        assert_eq!("empty = (\n    b''\n)", result);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn with_json_renderer() {
+        let expected = "[\n\
+                        \x20 {\"offset\":2,\"bytes\":[108,108,111,34],\"ascii\":\"llo\\\"\"},\n\
+                        \x20 {\"offset\":6,\"bytes\":[10],\"ascii\":\".\"}\n\
+                        ]";
+
+        let reader = Cursor::new(b"Hello\"\n".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .skip(2)
+            .with_renderer(Box::new(crate::JsonRenderer::new()))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_renderer_is_not_squeezed() {
+        let result = Dumper::new(Cursor::new(vec![0; 0x40]))
+            .squeeze(true)
+            .with_renderer(Box::new(crate::JsonRenderer::new()))
+            .render()
+            .unwrap();
+
+        let lines: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(4, lines.as_array().unwrap().len());
+    }
+
+    #[test]
+    fn with_partial_byte_group() {
+        let expected = "\x20        | 00       04       08   |           \n\
+                        ---------+------------------------+-----------\n\
+                        00000000 | 48656c6c 6f2c2077 6f72 | Hello, wor\n\
+                        0000000a | 6c6421                 | ld!";
+
+        let reader = Cursor::new(b"Hello, world!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(10)
+            .byte_group_length(4)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn header_byte_offsets() {
+        let header = |byte_group_length| {
+            let renderer = ClassicRenderer::new()
+                .line_width(12)
+                .byte_group_length(byte_group_length);
+            renderer
+                .header()
+                .unwrap()
+                .lines()
+                .next()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            "         | 00 01 02 03 04 05 06 07 08 09 0a 0b |             ",
+            header(1)
+        );
+        assert_eq!(
+            "         | 00   02   04   06   08   0a   |             ",
+            header(2)
+        );
+        assert_eq!(
+            "         | 00       04       08       |             ",
+            header(4)
+        );
+        assert_eq!(
+            "         | 00                   0a   |             ",
+            header(10)
+        );
+
+        // indices past 0xff need three digits, so only every other label fits when bytes aren't grouped
+        let renderer = ClassicRenderer::new().line_width(0x110);
+        let header = renderer.header().unwrap();
+        assert!(header.starts_with("         | 000   002   004"));
+    }
+
+    #[test]
+    fn with_byte_group_length_over_line_width() {
+        let expected = "\x20        | 00       |     \n\
+                        ---------+----------+-----\n\
+                        00000000 | 48656c6c | Hell\n\
+                        00000004 | 6f       | o";
+
+        let reader = Cursor::new(b"Hello".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .byte_group_length(8)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn line_width_auto_without_terminal() {
+        let bytes = (0..0x20).collect::<Vec<u8>>();
+
+        let expected = Dumper::new(Cursor::new(bytes.clone())).render().unwrap();
+        let result = Dumper::new(Cursor::new(bytes))
+            .line_width(8)
+            .line_width_auto(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn fit_line_width() {
+        let mut renderer = ClassicRenderer::new();
+        renderer.fit_line_width(80);
+        assert_eq!(16, renderer.line_width);
+
+        // each byte takes up 4 columns, with 13 more for the offset and separators
+        renderer.fit_line_width(120);
+        assert_eq!(26, renderer.line_width);
+
+        let mut renderer = ClassicRenderer::new().byte_group_length(4);
+        renderer.fit_line_width(120);
+        assert_eq!(32, renderer.line_width);
+    }
+
+    #[test]
+    fn max_line_width() {
+        assert_eq!(16, max_line_width_for(80, &LayoutOptions::new()));
+
+        // a group of 4 bytes takes up 13 columns and the gap every 8 bytes one more, so 28 bytes would need 107
+        let opts = LayoutOptions::new().byte_group_length(4).group_gap(Some(8));
+        assert_eq!(24, max_line_width_for(100, &opts));
+
+        let opts = LayoutOptions::new().show_offset(false).show_ascii(false);
+        assert_eq!(1, max_line_width_for(0, &opts));
+        assert_eq!(256, max_line_width_for(1000, &opts));
+    }
+
+    #[test]
+    fn without_header() {
+        let expected = "00000000 | 48 65 6c 6c 6f 2c 20 77 | Hello, w\n\
+                        00000008 | 6f 72 6c 64 21          | orld!";
+
+        let reader = Cursor::new(b"Hello, world!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .header(false)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_group_gap() {
+        let expected = "\x20        | 00 01 02 03  04 05 06 07  08 09 |           \n\
+                        ---------+---------------------------------+-----------\n\
+                        00000000 | 48 65 6c 6c  6f 2c 20 77  6f 72 | Hello, wor\n\
+                        0000000a | 6c 64 21                        | ld!";
+
+        let reader = Cursor::new(b"Hello, world!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(10)
+            .group_gap(Some(4))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn without_offset() {
+        let expected = "00 01 02 03 |     \n\
+                        ------------+-----\n\
+                        48 65 6c 6c | Hell\n\
+                        6f 21       | o!";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .show_offset(false)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn hex_only() {
+        let expected = "00 01 02 03\n\
+                        -----------\n\
+                        48 65 6c 6c\n\
+                        6f 21";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .show_offset(false)
+            .show_ascii(false)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_separators() {
+        let expected = "\x20       : 00 01 02 03      \n\
+                        --------+------------------\n\
+                        00000000: 48 65 6c 6c  Hell\n\
+                        00000004: 6f 21        o!";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .separators(SeparatorStyle {
+                offset: ": ".to_string(),
+                text: "  ".to_string(),
+                ..SeparatorStyle::classic()
+            })
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn compact() {
+        let expected = "00000000 48 65 6c 6c Hell\n\
+                        00000004 6f 21 o!";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .compact(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_line_numbers() {
+        let expected = "\x20 |          | 00 01 02 03 |     \n\
+                        --+----------+-------------+-----\n\
+                        1 | 00000002 | 6c 6c 6f 2c | llo,\n\
+                        2 | 00000006 | 20 77 6f 72 |  wor\n\
+                        3 | 0000000a | 6c 64 21    | ld!";
+
+        let reader = Cursor::new(b"Hello, world!".to_vec());
+        let result = Dumper::from_seekable(reader)
+            .line_width(4)
+            .skip(2)
+            .line_numbers(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_line_terminator() {
+        let dumper = || {
+            Dumper::new(Cursor::new(b"Hello!".to_vec()))
+                .line_width(4)
+                .compact(true)
+                .line_terminator(LineTerminator::CrLf)
+        };
+
+        let mut output = Vec::new();
+        dumper().dump_to(&mut output).unwrap();
+        assert_eq!(
+            b"00000000 48 65 6c 6c Hell\r\n00000004 6f 21 o!\r\n",
+            &output[..]
+        );
+
+        let mut output = Vec::new();
+        dumper()
+            .trailing_newline(false)
+            .dump_to(&mut output)
+            .unwrap();
+        assert_eq!(
+            b"00000000 48 65 6c 6c Hell\r\n00000004 6f 21 o!",
+            &output[..]
+        );
+    }
+
+    #[test]
+    fn render_matches_dump_without_trailing_newline() {
+        let bytes = b"Lorem ipsum dolor sit amet".to_vec();
+
+        let mut output = Vec::new();
+        Dumper::new(Cursor::new(bytes.clone()))
+            .trailing_newline(false)
+            .dump_to(&mut output)
+            .unwrap();
+        let expected = String::from_utf8(output).unwrap();
+
+        let result = Dumper::new(Cursor::new(bytes)).render().unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_histogram() {
+        let expected = "\x20        | 00 01 02 03 04 05 06 07 |         \n\
+                        ---------+-------------------------+---------\n\
+                        00000000 | 61 62 61 63 61 62 61 00 | abacaba.\n\
+                        -- 61 4  50.0% ################################\n\
+                        -- 62 2  25.0% ################\n\
+                        -- 00 1  12.5% ########\n\
+                        -- 63 1  12.5% ########";
+
+        let reader = Cursor::new(b"abacaba\0".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .histogram(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_collapse_fill() {
+        let expected = "00000000 | 41 42 43 44 | ABCD\n\
+                        00000004 | 00 00 45    | ..E\n\
+                        [0x9 bytes of 0x00 skipped]\n\
+                        00000010 | 46 47 48 49 | FGHI\n\
+                        00000014 | 4a          | J\n\
+                        [0x3 bytes of 0x00 skipped]";
+
+        let mut bytes = b"ABCD\0\0E".to_vec();
+        bytes.extend([0; 9]);
+        bytes.extend(b"FGHIJ\0\0\0");
+        let result = Dumper::new(Cursor::new(bytes))
+            .line_width(4)
+            .collapse_fill(Some(0), 3)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_stride() {
+        let expected = "00000000 | 00 00 00 00 | ....\n\
+                        00018000 | 18 18 18 18 | ....";
+
+        // the second line is past the bytes read ahead, so the seekable reader seeks to it
+        let bytes: Vec<u8> = (0..0x30000u32).map(|offset| (offset >> 12) as u8).collect();
+        let result = Dumper::new(Cursor::new(bytes.clone()))
+            .line_width(4)
+            .stride(0x6000)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+        assert_eq!(expected, result);
+
+        let result = Dumper::from_seekable(Cursor::new(bytes))
+            .line_width(4)
+            .stride(0x6000)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_style_fn() {
+        let expected = "00000010 | 00 \x1b[97;44m41\x1b[0m \x1b[38;2;255;128;0m0a\x1b[0m    \
+                        | .\x1b[97;44mA\x1b[0m\x1b[38;2;255;128;0m.\x1b[0m";
+
+        let reader = Cursor::new(vec![0x00, 0x41, 0x0a]);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .base_address(0x10)
+            .color(true)
+            .style_fn(|offset, byte| match (offset, byte) {
+                (0x11, _) => ByteStyle::new()
+                    .foreground(Color::BrightWhite)
+                    .background(Color::Blue),
+                (_, 0x0a) => ByteStyle::new().foreground(Color::Rgb(255, 128, 0)),
+                _ => ByteStyle::new(),
+            })
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_text_renderer() {
+        let expected = "00000000 | 00 01 0a 0b 25                                  | ABKL?";
+
+        let reader = Cursor::new(vec![0x00, 0x01, 0x0a, 0x0b, 0x25]);
+        let result = Dumper::new(reader)
+            .text_renderer(|byte| match byte {
+                0..=25 => (b'A' + byte) as char,
+                _ => '?',
+            })
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn wide_text_renderer_characters() {
+        let expected = "00000000 | 00 01 02 03                                     | A...";
+
+        let reader = Cursor::new(vec![0x00, 0x01, 0x02, 0x03]);
+        let result = Dumper::new(reader)
+            .text_renderer(|byte| match byte {
+                0 => 'A',
+                1 => '\u{4e2d}',
+                2 => '\u{301}',
+                _ => '\u{1f600}',
+            })
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_offset_formatter() {
+        let expected = "\x20         | 00 01 02 03 |     \n\
+                        ----------+-------------+-----\n\
+                        1234:fffe | 48 65 6c 6c | Hell\n\
+                        1235:0002 | 6f 21       | o!";
+
+        let reader = Cursor::new(b"Hello!".to_vec());
+        let result = Dumper::from_seekable(reader)
+            .line_width(4)
+            .base_address(0x1234fffe)
+            .offset_formatter(|offset| format!("{:04x}:{:04x}", offset >> 16, offset & 0xffff))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn from_bytes() {
+        let expected = Dumper::new(Cursor::new(b"Hello!".to_vec()))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, Dumper::from_bytes(b"Hello!").render().unwrap());
+        assert_eq!(expected, Dumper::from(&b"Hello!"[..]).render().unwrap());
+        assert_eq!(expected, Dumper::from(b"Hello!".to_vec()).render().unwrap());
+    }
+
+    #[test]
+    fn with_range() {
+        let expected = "00000002 | 6c 6c 6f 2c | llo,\n\
+                        00000006 | 20          |  ";
+
+        let result = Dumper::from_bytes(b"Hello, world!")
+            .line_width(4)
+            .range(2, 7)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+
+        let result = Dumper::from_bytes(b"Hello, world!").try_range(7, 2);
+        assert!(matches!(
+            result,
+            Err(DumperError::InvalidRange { start: 7, end: 2 })
+        ));
+    }
+
+    #[test]
+    fn read_buffer_stops_at_limits() {
+        /// A reader returning at most five bytes per call, like a pipe that data trickles into
+        struct Trickle(Cursor<Vec<u8>>);
+
+        impl Read for Trickle {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let length = buf.len().min(5);
+                self.0.read(&mut buf[..length])
+            }
+        }
+
+        let bytes: Vec<u8> = (0..0x100u32).map(|i| i as u8).collect();
+        let expected = Dumper::from_bytes(&bytes[..40]).render().unwrap();
+
+        let mut reader = Trickle(Cursor::new(bytes.clone()));
+        let result = Dumper::new(&mut reader)
+            .byte_count(Some(40))
+            .render()
+            .unwrap();
+        assert_eq!(expected, result);
+        assert_eq!(40, reader.0.position());
+
+        let mut reader = Cursor::new(bytes);
+        Dumper::new(&mut reader)
+            .line_count(Some(3))
+            .render()
+            .unwrap();
+        assert_eq!(0x30, reader.position());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn render_parallel() {
+        let bytes: Vec<u8> = (0..0x90000u32)
+            .map(|i| (i.wrapping_mul(0x9e3779b1) >> 24) as u8)
+            .collect();
+        let options = |bytes| {
+            Dumper::from_bytes(bytes)
+                .line_width(12)
+                .skip(5)
+                .byte_count(Some(0x80003))
+                .line_numbers(true)
+                .line_terminator(LineTerminator::CrLf)
+        };
+
+        let expected = options(&bytes).render().unwrap();
+        assert_eq!(expected, options(&bytes).render_parallel().unwrap());
+        assert_eq!(
+            Dumper::from_bytes(&[]).html(true).render().unwrap(),
+            Dumper::from_bytes(&[])
+                .html(true)
+                .render_parallel()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn with_html() {
+        let expected = "<pre class=\"rxd\">\n\
+                        \x20        | 00 01 |   \n\
+                        ---------+-------+---\n\
+                        00000000 | <span class=\"rxd-printable\">3c</span> \
+                        <span class=\"rxd-null\">00</span> | \
+                        <span class=\"rxd-printable\">&lt;</span><span class=\"rxd-null\">.</span>\n\
+                        </pre>";
+
+        let reader = Cursor::new(vec![0x3c, 0x00]);
+        let result = Dumper::new(reader)
+            .line_width(2)
+            .html(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn html_escapes_annotations() {
+        let expected = "<pre class=\"rxd\">\n\
+                        &lt;0&gt; | <span class=\"rxd-printable\">41</span> | \
+                        <span class=\"rxd-printable\">A</span> ; &lt;b&gt; &amp; &quot;i&quot;\n\
+                        </pre>";
+
+        let result = Dumper::new(Cursor::new(b"A".to_vec()))
+            .line_width(1)
+            .header(false)
+            .html(true)
+            .offset_formatter(|offset| format!("<{offset}>"))
+            .annotate(vec![(0..1, "<b> & \"i\"".to_string())])
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_text_encoding() {
+        let expected = "00000000 | 63 61 66 e9 85 | café.";
+
+        let reader = Cursor::new(vec![0x63, 0x61, 0x66, 0xe9, 0x85]);
+        let result = Dumper::new(reader)
+            .line_width(5)
+            .text_encoding(TextEncoding::Latin1)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+
+        let expected = "00000000 | 63 61 66 82 b0 | café░";
+
+        let reader = Cursor::new(vec![0x63, 0x61, 0x66, 0x82, 0xb0]);
+        let result = Dumper::new(reader)
+            .line_width(5)
+            .text_encoding(TextEncoding::CodePage437)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_ebcdic() {
+        let expected = "00000000 | c8 85 93 93 96 40 5a 25 | Hello !.";
+
+        let reader = Cursor::new(vec![0xc8, 0x85, 0x93, 0x93, 0x96, 0x40, 0x5a, 0x25]);
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .control_pictures(true)
+            .text_encoding(TextEncoding::Ebcdic)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_little_endian() {
+        let expected = "00000000 | 12345678 0000abcd 00ff | xV4.......\n\
+                        0000000a | 00010000 02010000      | ........";
+
+        let bytes = vec![
+            0x78, 0x56, 0x34, 0x12, 0xcd, 0xab, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00,
+            0x00, 0x00, 0x01, 0x02,
+        ];
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(10)
+            .byte_group_length(4)
+            .endian(Endian::Little)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_decode_as() {
+        let expected = "         | 00       04       |          |                      \n\
+                        ---------+-------------------+----------+----------------------\n\
+                        00000000 | 12345678 ffffffff | xV4..... |  305419896 4294967295\n\
+                        00000008 | 00000001          | ....     |          1";
+
+        let bytes = vec![
+            0x78, 0x56, 0x34, 0x12, 0xff, 0xff, 0xff, 0xff, 0x01, 0x00, 0x00, 0x00,
+        ];
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .byte_group_length(4)
+            .endian(Endian::Little)
+            .decode_as(Some(IntWidth::U32))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_decode_float() {
+        let expected = "         | 00       04       |          |                        \n\
+                        ---------+-------------------+----------+------------------------\n\
+                        00000000 | 40490fd0 c2f6e979 | @I.....y |       3.142    -123.456\n\
+                        00000008 | 7f7fffff          | ....     |    3.403e38";
+
+        let bytes = vec![
+            0x40, 0x49, 0x0f, 0xd0, 0xc2, 0xf6, 0xe9, 0x79, 0x7f, 0x7f, 0xff, 0xff,
+        ];
+        let reader = Cursor::new(bytes);
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .byte_group_length(4)
+            .decode_float(Some(FloatWidth::F32))
+            .float_precision(3)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+
+        let result = Dumper::new(Cursor::new(Vec::new()))
+            .byte_group_length(2)
+            .try_decode_float(Some(FloatWidth::F64));
+        assert!(matches!(
+            result,
+            Err(DumperError::FloatWidthMismatch {
+                float_width: FloatWidth::F64,
+                byte_group_length: 2,
+            })
+        ));
+    }
+
+    #[test]
+    fn with_offset_both() {
+        let expected = "              | 00 01 02 03 |     \n\
+                        --------------+-------------+-----\n\
+                        00000004 (4)  | 65 6d 20 69 | em i\n\
+                        00000008 (8)  | 70 73 75 6d | psum\n\
+                        0000000c (12) | 20 64       |  d";
+
+        let reader = Cursor::new(b"Lorem ipsum dolor".to_vec());
+        let result = Dumper::from_seekable(reader)
+            .line_width(4)
+            .skip(3)
+            .base_address(1)
+            .byte_count(Some(10))
+            .offset_both(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_ascii_width() {
+        let expected = "         | 00 01 02 03 04 05 06 07 |     \n\
+                        ---------+-------------------------+-----\n\
+                        00000000 | 4c 6f 72 65 6d 20 69 70 | Lore\n\
+                        00000008 | 73 75 6d                | sum";
+
+        let reader = Cursor::new(b"Lorem ipsum".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .ascii_width(Some(4))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_short_line_filler() {
+        let expected = "         | 00 01 02 03  04 05 06 07 |         \n\
+                        ---------+--------------------------+---------\n\
+                        00000000 | 4c 6f 72 65  6d 20 69 70 | Lorem ip\n\
+                        00000008 | 73 75 6d --  -- -- -- -- | sum";
+
+        let reader = Cursor::new(b"Lorem ipsum".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .group_gap(Some(4))
+            .short_line_filler(Some("--"))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn layout() {
+        let expected = Layout {
+            offset_width: 11,
+            hex_column_start: 14,
+            hex_column_width: 23,
+            ascii_column_start: Some(40),
+            total_width: 48,
+        };
+
+        let reader = Cursor::new(b"Lorem ipsum".to_vec());
+        let mut dumper = Dumper::new(reader)
+            .line_width(8)
+            .offset_base(OffsetBase::Octal);
+        let result = dumper.render().unwrap();
+        let layout = dumper.layout();
+        assert_eq!(expected, layout);
+
+        let line = result.lines().nth(2).unwrap();
+        assert_eq!(line.len(), layout.total_width);
+        assert!(line[layout.hex_column_start..].starts_with("4c 6f"));
+        assert!(line[layout.ascii_column_start.unwrap()..].starts_with("Lorem"));
+    }
+
+    #[test]
+    fn with_offset_width() {
+        let expected = "     | 00 01 02 03 04 05 06 07 |         \n\
+                        -----+-------------------------+---------\n\
+                        0000 | 4c 6f 72 65 6d 20 69 70 | Lorem ip\n\
+                        0008 | 73 75 6d                | sum";
+
+        let reader = Cursor::new(b"Lorem ipsum".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .offset_width(Some(4))
+            .render()
+            .unwrap();
+        assert_eq!(expected, result);
+
+        let reader = Cursor::new(vec![0; 0x20]);
+        let err = Dumper::new(reader)
+            .base_address(0xf0)
+            .offset_width(Some(2))
+            .render()
+            .unwrap_err();
+        assert_eq!(
+            "offset 0x100 doesn't fit in an offset column of 2 digits",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn with_annotations() {
+        let expected = "         | 00 01 02 03 04 05 06 07 |         \n\
+                        ---------+-------------------------+---------\n\
+                        00000000 | 7f 45 4c 46 02 01 01 00 | .ELF.... ; magic, class\n\
+                        00000008 | 00 00 00 00 00 00 00 00 | ........\n\
+                        00000010 | 02 00                   | ..       ; type";
+
+        let bytes = b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x02\0".to_vec();
+        let result = Dumper::new(Cursor::new(bytes))
+            .line_width(8)
+            .annotate(vec![
+                (0..4, "magic".to_string()),
+                (4..5, "class".to_string()),
+                (0x10..0x12, "type".to_string()),
+            ])
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_region() {
+        let annotations = vec![(0..4, "magic".to_string()), (4..0xa, "header".to_string())];
+        let bytes: Vec<u8> = (0..0x10).collect();
+
+        let expected = "00000004 | 04 05 06 07 | .... ; header\n\
+                        00000008 | 08 09       | ..   ; header";
+        let result = Dumper::new(Cursor::new(bytes.clone()))
+            .line_width(4)
+            .annotate(annotations.clone())
+            .region("header")
+            .format_contents()
+            .unwrap()
+            .join("\n");
+        assert_eq!(expected, result);
+
+        let expected = "00000000 | 04 05 06 07 | .... ; magic\n\
+                        00000004 | 08 09       | ..   ; header";
+        let result = Dumper::new(Cursor::new(bytes.clone()))
+            .line_width(4)
+            .annotate(annotations.clone())
+            .region("header")
+            .relative_offsets(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+        assert_eq!(expected, result);
+
+        let result = Dumper::new(Cursor::new(bytes))
+            .annotate(annotations)
+            .try_region("palette");
+        assert!(matches!(result, Err(DumperError::UnknownRegion(name)) if name == "palette"));
+    }
+
+    #[test]
+    fn with_radix() {
+        let expected = "         | 00       01       02       03       |     \n\
+                        ---------+-------------------------------------+-----\n\
+                        00000000 | 01001100 01101111 01110010 01100101 | Lore\n\
+                        00000004 | 01101101                            | m";
+
+        let reader = Cursor::new(b"Lorem".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .radix(Radix::Binary)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+
+        let expected = "00000000 | 114157 162145 | Lore\n\
+                        00000004 | 155           | m";
+
+        let reader = Cursor::new(b"Lorem".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .byte_group_length(2)
+            .radix(Radix::Octal)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_decimal_radix() {
+        let expected = "         | 00     02     04     |       \n\
+                        ---------+----------------------+-------\n\
+                        00000000 | 076111 114101 109000 | Lorem.\n\
+                        00000006 | 255                  | .";
+
+        let reader = Cursor::new(vec![0x4c, 0x6f, 0x72, 0x65, 0x6d, 0x00, 0xff]);
+        let result = Dumper::new(reader)
+            .line_width(6)
+            .byte_group_length(2)
+            .radix(Radix::Decimal)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_signed_decimal_radix() {
+        let expected = "         | 00   01   02   03   |     \n\
+                        ---------+---------------------+-----\n\
+                        00000000 | +000 +127 -128 -001 | ....";
+
+        let reader = Cursor::new(vec![0x00, 0x7f, 0x80, 0xff]);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .radix(Radix::SignedDecimal)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_escapes() {
+        let expected = "         | 00 01 02 03 04 05 |             \n\
+                        ---------+-------------------+-------------\n\
+                        00000000 | 48 69 0a 09 00 5c |  H i\\n\\t\\0\\\\\n\
+                        00000006 | 0d 7f             | \\r .";
+
+        let reader = Cursor::new(b"Hi\n\t\0\\\r\x7f".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(6)
+            .text_mode(TextMode::Escapes)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_utf8_text() {
+        let expected = "00000000 | 61 c3 a9 e6 bc a2 ff 62 | aé 漢 \u{fffd}b\n\
+                        00000008 | e2 82 ac 21 e2 82       | €  !\u{fffd} ";
+
+        let reader = Cursor::new(b"a\xc3\xa9\xe6\xbc\xa2\xffb\xe2\x82\xac!\xe2\x82".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .text_mode(TextMode::Utf8)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_reverse_line() {
+        let expected = "00000000 | 03 02 01 00 | ....\n\
+                        00000004 | 43 42 41 40 | CBA@\n\
+                        00000008 | 62 61       | ba";
+
+        let reader = Cursor::new(vec![0, 1, 2, 3, 0x40, 0x41, 0x42, 0x43, b'a', b'b']);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .reverse_line(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn reverse_line_header() {
+        let expected = "\x20        | 07 06 05 04 03 02 01 00 |         \n\
+                        ---------+-------------------------+---------\n\
+                        00000000 | 07 06 05 04 03 02 01 00 | ........";
+
+        let reader = Cursor::new((0..8).collect::<Vec<u8>>());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .reverse_line(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_placeholder() {
+        let expected = "00000000 | 2e 00 41 ff | .·A·";
+
+        let reader = Cursor::new(vec![0x2e, 0x00, 0x41, 0xff]);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .placeholder('·')
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_control_picture_for_delete() {
+        let expected = "00000000 | 7e 7f | ~\u{2421}";
+
+        let reader = Cursor::new(vec![0x7e, 0x7f]);
+        let result = Dumper::new(reader)
+            .line_width(2)
+            .control_pictures(true)
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_grep() {
+        let expected = "00000004 | 6d 20 69 70 | m ip\n\
+                        00000008 | 73 75 6d 20 | sum\x20";
+
+        let reader = Cursor::new(b"Lorem ipsum dolor".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .grep(b"m".to_vec())
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_highlight() {
+        let expected = "00000000 | 61 \x1b[7m62\x1b[27m \x1b[7m63\x1b[27m | a\x1b[7mb\x1b[27m\x1b[7mc\x1b[27m\n\
+                        00000003 | \x1b[7m61\x1b[27m \x1b[7m62\x1b[27m \x1b[7m63\x1b[27m \
+                        | \x1b[7ma\x1b[27m\x1b[7mb\x1b[27m\x1b[7mc\x1b[27m\n\
+                        00000006 | \x1b[7m61\x1b[27m 62 63 | \x1b[7ma\x1b[27mbc";
+
+        let reader = Cursor::new(b"abcabcabc".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(3)
+            .highlight(b"bca".to_vec())
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_summary() {
+        let expected = "         | 00 01 02 03 04 05 06 07 |         \n\
+                        ---------+-------------------------+---------\n\
+                        00000000 | 31 32 33 34 35 36 37 38 | 12345678\n\
+                        00000008 | 39                      | 9\n\
+                        -- 9 bytes, crc32=cbf43926";
+
+        let reader = Cursor::new(b"123456789".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .summary(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+
+        let expected = "3132333435363738\n39";
+
+        let reader = Cursor::new(b"123456789".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(8)
+            .summary(true)
+            .with_renderer(Box::new(PlainRenderer::new()))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_entropy() {
+        let expected = "         | 00 01 02 03 |      |     \n\
+                        ---------+-------------+------+-----\n\
+                        00000000 | 00 00 00 00 | .... | 0.00\n\
+                        00000004 | 00 01 02 03 | .... | 2.00\n\
+                        00000008 | 61 61 62    | aab  | 0.92";
+
+        let reader = Cursor::new(vec![0, 0, 0, 0, 0, 1, 2, 3, b'a', b'a', b'b']);
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .entropy(true)
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn with_line_checksum() {
+        let expected = "         | 00 01 02 03 |      | crc8\n\
+                        ---------+-------------+------+-----\n\
+                        00000000 | 31 32 33 34 | 1234 | c2\n\
+                        00000004 | 35 36 37 38 | 5678 | 41\n\
+                        00000008 | 39          | 9    | af";
+
+        let reader = Cursor::new(b"123456789".to_vec());
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .line_checksum(Some(ChecksumKind::Crc8))
+            .render()
+            .unwrap();
+        assert_eq!(expected, result);
+
+        assert_eq!(0xf4, ChecksumKind::Crc8.checksum(b"123456789"));
+        assert_eq!(0x31, ChecksumKind::Xor.checksum(b"123456789"));
+    }
+
+    #[test]
+    fn unseekable_reader() {
+        let expected = "00000003 | 6c 6f 2c 20 | lo,\x20\n\
+                        00000007 | 77 6f       | wo";
+
+        // a slice only implements `Read`, so the skipped bytes have to be read and discarded
+        let reader: &[u8] = b"Hello, world";
+        let result = Dumper::new(reader)
+            .line_width(4)
+            .skip(3)
+            .byte_count(Some(6))
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+
+        let expected = "const HELLO: &[u8] = &[\n\
+                        \x20   0x48, 0x65,\n\
+                        ];";
+
+        let reader: &[u8] = b"He";
+        let result = Dumper::new(reader)
+            .with_renderer(Box::new(RustArrayRenderer::new("HELLO")))
+            .render()
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn seekable_skip() {
+        // counts the bytes read, to check that skipped bytes are seeked past rather than read
+        struct CountingReader {
+            inner: Cursor<Vec<u8>>,
+            bytes_read: Rc<Cell<usize>>,
+        }
+
+        impl Read for CountingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let length = self.inner.read(buf)?;
+                self.bytes_read.set(self.bytes_read.get() + length);
+                Ok(length)
+            }
+        }
+
+        impl Seek for CountingReader {
+            fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+                self.inner.seek(pos)
+            }
+        }
+
+        let expected = "00000006 | 20 77 6f 72 |  wor";
+
+        let bytes_read = Rc::new(Cell::new(0));
+        let reader = CountingReader {
+            inner: Cursor::new(b"Hello, world".to_vec()),
+            bytes_read: Rc::clone(&bytes_read),
+        };
+        let result = Dumper::from_seekable(reader)
+            .line_width(4)
+            .skip(6)
+            .line_count(Some(1))
+            .format_contents()
+            .unwrap()
+            .join("\n");
+
+        assert_eq!(expected, result);
+        assert_eq!(4, bytes_read.get());
+    }
+
+    #[test]
+    fn matches_render_lines() {
+        let bytes = b"Lorem ipsum dolor sit amet";
+
+        let expected = Dumper::new(&bytes[..])
+            .line_width(8)
+            .base_address(0x100)
+            .render()
+            .unwrap();
+
+        let mut renderer = ClassicRenderer::new().line_width(8);
+        let result = crate::render_lines(&mut renderer, bytes, 8, 0x100).join("\n");
+
+        assert_eq!(expected, result);
+    }
+}
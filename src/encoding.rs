@@ -0,0 +1,71 @@
+/// Character set used to decode the bytes shown in the text column
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// Printable ASCII characters only
+    #[default]
+    Ascii,
+    /// [ISO-8859-1], which adds printable characters for `0xa0..=0xff`
+    ///
+    /// [ISO-8859-1]: https://en.wikipedia.org/wiki/ISO/IEC_8859-1
+    Latin1,
+    /// [Code page 437], the character set of the original IBM PC, which adds characters for `0x80..=0xff`
+    ///
+    /// [Code page 437]: https://en.wikipedia.org/wiki/Code_page_437
+    CodePage437,
+    /// [EBCDIC code page 037], used on IBM mainframes in the US and Canada
+    ///
+    /// [EBCDIC code page 037]: https://en.wikipedia.org/wiki/EBCDIC_037
+    Ebcdic,
+}
+
+impl TextEncoding {
+    /// Decode `byte` into the character it represents, or `None` if it isn't printable in this encoding
+    pub(crate) fn decode(self, byte: u8) -> Option<char> {
+        match (self, byte) {
+            (TextEncoding::Ebcdic, _) => Some(CODE_PAGE_037[byte as usize]).filter(|&c| c != '\0'),
+            (_, 0x20..=0x7e) => Some(byte as char),
+            (TextEncoding::Latin1, 0xa0..=0xff) => Some(byte as char),
+            (TextEncoding::CodePage437, 0x80..=0xff) => Some(CODE_PAGE_437[byte as usize - 0x80]),
+            _ => None,
+        }
+    }
+
+    /// Whether the bytes `0x00..=0x7f` have the same meaning as in ASCII
+    pub(crate) fn is_ascii_compatible(self) -> bool {
+        self != TextEncoding::Ebcdic
+    }
+}
+
+/// Characters of code page 437 for the bytes `0x80..=0xff`
+#[rustfmt::skip]
+const CODE_PAGE_437: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Characters of EBCDIC code page 037 for every byte, with `'\0'` marking control codes
+#[rustfmt::skip]
+const CODE_PAGE_037: [char; 256] = [
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    ' ', '\u{a0}', 'â', 'ä', 'à', 'á', 'ã', 'å', 'ç', 'ñ', '¢', '.', '<', '(', '+', '|',
+    '&', 'é', 'ê', 'ë', 'è', 'í', 'î', 'ï', 'ì', 'ß', '!', '$', '*', ')', ';', '¬',
+    '-', '/', 'Â', 'Ä', 'À', 'Á', 'Ã', 'Å', 'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?',
+    'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', '`', ':', '#', '@', '\'', '=', '"',
+    'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', '«', '»', 'ð', 'ý', 'þ', '±',
+    '°', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 'ª', 'º', 'æ', '¸', 'Æ', '¤',
+    'µ', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®',
+    '^', '£', '¥', '·', '©', '§', '¶', '¼', '½', '¾', '[', ']', '¯', '¨', '´', '×',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '\0', 'ô', 'ö', 'ò', 'ó', 'õ',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ',
+    '\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '³', 'Û', 'Ü', 'Ù', 'Ú', '\0',
+];
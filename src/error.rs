@@ -0,0 +1,98 @@
+use crate::{FloatWidth, IntWidth};
+use std::{error, fmt, io};
+
+/// Errors produced while configuring or running a [`Dumper`](crate::Dumper)
+#[derive(Debug)]
+pub enum DumperError {
+    /// The number of bytes per line was outside the range 1-256
+    InvalidLineWidth(usize),
+    /// The number of bytes per group was outside the range 1-256
+    InvalidByteGroupLength(usize),
+    /// The placeholder for unprintable bytes wasn't a single column wide
+    InvalidPlaceholder(char),
+    /// The number of bytes per group didn't match the width of the integers to decode
+    IntWidthMismatch {
+        int_width: IntWidth,
+        byte_group_length: usize,
+    },
+    /// The number of bytes per group didn't match the width of the floats to decode
+    FloatWidthMismatch {
+        float_width: FloatWidth,
+        byte_group_length: usize,
+    },
+    /// The end of the range of bytes to dump was before its start
+    InvalidRange { start: u64, end: u64 },
+    /// No annotated region had the label of the region to dump
+    UnknownRegion(String),
+    /// An offset had more digits than the width the offset column was fixed to
+    OffsetTooWide { offset: u64, width: usize },
+    /// The offsets of the dumped bytes went past the largest 64-bit offset, e.g. because of a high base address
+    OffsetOverflow,
+    /// A line of a hex dump couldn't be parsed
+    Parse { line: usize, message: String },
+    /// Reading the input or writing the output failed
+    Io(io::Error),
+}
+
+impl fmt::Display for DumperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumperError::InvalidLineWidth(width) => {
+                write!(f, "line width must be in the range 1-256, got {width}")
+            }
+            DumperError::InvalidByteGroupLength(length) => {
+                write!(
+                    f,
+                    "byte group length must be in the range 1-256, got {length}"
+                )
+            }
+            DumperError::InvalidPlaceholder(placeholder) => {
+                write!(
+                    f,
+                    "placeholder must be a single column wide character, got {placeholder:?}"
+                )
+            }
+            DumperError::IntWidthMismatch {
+                int_width,
+                byte_group_length,
+            } => write!(
+                f,
+                "decoding {int_width:?} integers requires a byte group length of {}, got {byte_group_length}",
+                int_width.bytes()
+            ),
+            DumperError::FloatWidthMismatch {
+                float_width,
+                byte_group_length,
+            } => write!(
+                f,
+                "decoding {float_width:?} floats requires a byte group length of {}, got {byte_group_length}",
+                float_width.bytes()
+            ),
+            DumperError::InvalidRange { start, end } => {
+                write!(f, "range end {end:#x} is before its start {start:#x}")
+            }
+            DumperError::UnknownRegion(name) => write!(f, "no annotated region is labelled {name:?}"),
+            DumperError::OffsetTooWide { offset, width } => {
+                write!(f, "offset {offset:#x} doesn't fit in an offset column of {width} digits")
+            }
+            DumperError::OffsetOverflow => write!(f, "offsets past {:#x} can't be dumped", u64::MAX),
+            DumperError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            DumperError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl error::Error for DumperError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DumperError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DumperError {
+    fn from(err: io::Error) -> Self {
+        DumperError::Io(err)
+    }
+}
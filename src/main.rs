@@ -1,52 +1,989 @@
-use clap::Parser;
-use rxd::Dumper;
-use std::fs::File;
-use std::io::BufReader;
+use clap::{ArgGroup, Parser, ValueEnum};
+use rxd::{
+    diff, extract_strings, undump, undump_plain, Base64Renderer, CIncludeRenderer, ChecksumKind,
+    ColorChoice, DiffOptions, Dumper, DumperConfig, Endian, FloatWidth, IntWidth, LineTerminator,
+    PlainRenderer, PythonBytesRenderer, Radix, RustArrayRenderer, StringsOptions, TextEncoding,
+    TextMode,
+};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{
+    self, BufReader, BufWriter, Cursor, IsTerminal, LineWriter, Read, Seek, SeekFrom, Write,
+};
+use std::ops::Range;
 use std::process;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
 
-// TODO: add arg for byte count
+#[derive(Clone, Copy, ValueEnum)]
+enum ColorWhen {
+    Always,
+    Never,
+    Auto,
+}
+
+impl From<ColorWhen> for ColorChoice {
+    fn from(when: ColorWhen) -> Self {
+        match when {
+            ColorWhen::Always => ColorChoice::Always,
+            ColorWhen::Never => ColorChoice::Never,
+            ColorWhen::Auto => ColorChoice::Auto,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Encoding {
+    Ascii,
+    Latin1,
+    Cp437,
+    Ebcdic,
+}
+
+impl From<Encoding> for TextEncoding {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Ascii => TextEncoding::Ascii,
+            Encoding::Latin1 => TextEncoding::Latin1,
+            Encoding::Cp437 => TextEncoding::CodePage437,
+            Encoding::Ebcdic => TextEncoding::Ebcdic,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DecodeWidth {
+    U16,
+    U32,
+    U64,
+}
+
+impl From<DecodeWidth> for IntWidth {
+    fn from(width: DecodeWidth) -> Self {
+        match width {
+            DecodeWidth::U16 => IntWidth::U16,
+            DecodeWidth::U32 => IntWidth::U32,
+            DecodeWidth::U64 => IntWidth::U64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DecodeFloatWidth {
+    F32,
+    F64,
+}
+
+impl From<DecodeFloatWidth> for FloatWidth {
+    fn from(width: DecodeFloatWidth) -> Self {
+        match width {
+            DecodeFloatWidth::F32 => FloatWidth::F32,
+            DecodeFloatWidth::F64 => FloatWidth::F64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LineChecksum {
+    Xor,
+    Crc8,
+}
+
+impl From<LineChecksum> for ChecksumKind {
+    fn from(kind: LineChecksum) -> Self {
+        match kind {
+            LineChecksum::Xor => ChecksumKind::Xor,
+            LineChecksum::Crc8 => ChecksumKind::Crc8,
+        }
+    }
+}
+
+/// Number of bytes per line, or `auto` to fit the terminal
+#[derive(Clone, Copy)]
+enum LineWidth {
+    Auto,
+    Bytes(usize),
+}
+
+impl FromStr for LineWidth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(LineWidth::Auto),
+            _ => parse_byte_length(s).map(LineWidth::Bytes),
+        }
+    }
+}
+
+impl fmt::Display for LineWidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LineWidth::Auto => write!(f, "auto"),
+            LineWidth::Bytes(line_width) => write!(f, "{line_width}"),
+        }
+    }
+}
 
 #[derive(Parser)]
-#[command(version)]
+#[command(version, group(ArgGroup::new("layout").multiple(false)))]
 struct Args {
-    /// input file
-    file_path: String,
+    /// input files, or `-` to read from standard input (the default)
+    file_paths: Vec<String>,
+
+    /// keep counting offsets across files as if they were concatenated, instead of starting each file at zero
+    #[arg(long)]
+    continuous: bool,
 
-    /// offset of the byte to start dumping at
-    #[arg(short, default_value_t = 0)]
-    start_offset: u64,
+    /// only dump the bytes from offset START up to END
+    #[arg(long, value_name = "START:END", value_parser = parse_range, conflicts_with_all = ["skip", "byte_count"])]
+    range: Option<(u64, u64)>,
+
+    /// number of bytes to skip before dumping
+    #[arg(short = 's', long = "seek", value_name = "OFFSET", default_value_t = 0, value_parser = parse_size)]
+    skip: u64,
 
     /// number of lines to print
-    #[arg(short)]
+    #[arg(short = 'n', long = "lines")]
     line_count: Option<usize>,
 
-    /// number of bytes per line
-    #[arg(short = 'w', default_value_t = 16)]
-    line_width: usize,
+    /// number of bytes to print
+    #[arg(short = 'l', long = "length", value_parser = parse_size)]
+    byte_count: Option<u64>,
+
+    /// only print every Nth line, skipping the lines in between, for a quick overview of a large file
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    stride: usize,
+
+    /// collapse runs of identical lines into a single `*` line
+    #[arg(long)]
+    squeeze: bool,
+
+    /// say how many bytes each `*` line of `--squeeze` left out, e.g. `*  (0x30 bytes identical)`
+    #[arg(long)]
+    squeeze_count: bool,
+
+    /// print a blank line after every this many lines, splitting the dump into blocks
+    #[arg(long, value_name = "LINES")]
+    block_spacing: Option<usize>,
+
+    /// print every line, even if `--squeeze` is also given
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// only print lines containing this string
+    #[arg(long, value_name = "STRING")]
+    grep: Option<String>,
+
+    /// highlight every occurrence of this string
+    #[arg(long, value_name = "STRING")]
+    highlight: Option<String>,
+
+    /// label the lines with bytes from offset START up to END with a `; LABEL` comment, can be repeated
+    #[arg(long, value_name = "START:END=LABEL", value_parser = parse_annotation)]
+    annotate: Vec<(Range<u64>, String)>,
+
+    /// only dump the bytes of the `--annotate` region with this label
+    #[arg(long, value_name = "LABEL", requires = "annotate", conflicts_with_all = ["range", "skip", "byte_count"])]
+    region: Option<String>,
+
+    /// count the printed offsets from the first byte dumped instead of from the start of the file
+    #[arg(long)]
+    relative_offsets: bool,
+
+    /// print the number of bytes and their CRC-32 checksum after the dump
+    #[arg(long)]
+    summary: bool,
+
+    /// print a histogram of the most common bytes after the dump
+    #[arg(long)]
+    histogram: bool,
+
+    /// number of bytes per line, or `auto` to fit the terminal
+    #[arg(short = 'w', long = "width", value_name = "BYTES", default_value_t = LineWidth::Bytes(16))]
+    line_width: LineWidth,
+
+    /// follow each offset with its decimal value, e.g. `00000010 (16)`
+    #[arg(long)]
+    offset_both: bool,
+
+    /// number of digits of every offset, instead of fitting the offset column to the input
+    #[arg(long, value_name = "DIGITS")]
+    offset_width: Option<usize>,
 
     /// number of bytes grouped together per line
-    #[arg(short = 'g', default_value_t = 1)]
+    #[arg(short = 'g', long = "group-size", value_name = "BYTES", default_value_t = 1, value_parser = parse_byte_length)]
     byte_group_length: usize,
 
+    /// insert an extra space between groups after every this many bytes
+    #[arg(long, value_name = "BYTES")]
+    group_gap: Option<usize>,
+
+    /// start each line with its line number
+    #[arg(long)]
+    line_numbers: bool,
+
+    /// don't print the row of byte offsets and the separator row before the first line
+    #[arg(long)]
+    no_header: bool,
+
+    /// don't print the offset column
+    #[arg(long)]
+    no_offset: bool,
+
+    /// don't print the text column
+    #[arg(long)]
+    no_ascii: bool,
+
+    /// maximum number of bytes of each line shown in the text column
+    #[arg(long, value_name = "BYTES")]
+    ascii_width: Option<usize>,
+
+    /// text shown in place of each byte missing from a short last line, e.g. `--`
+    #[arg(long, value_name = "FILLER")]
+    short_line_filler: Option<String>,
+
+    /// leave out the header and padding, separating columns with single spaces
+    #[arg(long)]
+    compact: bool,
+
+    /// display bytes in binary instead of hex
+    #[arg(short = 'b', long)]
+    bits: bool,
+
+    /// display bytes in zero-padded decimal instead of hex
+    #[arg(short = 'd', long, conflicts_with = "bits")]
+    decimal: bool,
+
+    /// display bytes as signed decimals from -128 to 127 instead of hex
+    #[arg(long, conflicts_with = "bits")]
+    signed: bool,
+
+    /// display each group of bytes in little-endian order
+    #[arg(short = 'e', long)]
+    little_endian: bool,
+
+    /// display the bytes of each line in reverse order, with the offset still marking the start of the line
+    #[arg(long)]
+    reverse_line: bool,
+
+    /// also show each group of bytes as a decimal integer of this width
+    #[arg(long, value_enum)]
+    decode: Option<DecodeWidth>,
+
+    /// also show each group of bytes as a float of this width
+    #[arg(long, value_enum)]
+    decode_float: Option<DecodeFloatWidth>,
+
+    /// number of digits after the decimal point of decoded floats
+    #[arg(long, value_name = "DIGITS", default_value_t = 5)]
+    float_precision: usize,
+
+    /// also show the entropy of each line in bits per byte
+    #[arg(long)]
+    entropy: bool,
+
+    /// also show a checksum of each line
+    #[arg(long, value_enum)]
+    line_checksum: Option<LineChecksum>,
+
     /// display C0 control codes as characters
     #[arg(short)]
     control_pictures: bool,
+
+    /// character shown for bytes that can't be printed
+    #[arg(long, default_value_t = '.')]
+    placeholder: char,
+
+    /// show common control codes as C-style escapes in the text column
+    #[arg(long)]
+    escapes: bool,
+
+    /// decode the text column as UTF-8
+    #[arg(long, conflicts_with = "escapes")]
+    utf8: bool,
+
+    /// character set used to decode the text column
+    #[arg(long, value_enum, default_value_t = Encoding::Ascii)]
+    encoding: Encoding,
+
+    /// use uppercase hex digits
+    #[arg(short)]
+    uppercase: bool,
+
+    /// output only the bytes in hex, without offsets or ASCII
+    #[arg(short, long, group = "layout")]
+    plain: bool,
+
+    /// output the bytes as a C array declaration
+    #[arg(short = 'i', long, group = "layout")]
+    include: bool,
+
+    /// output the bytes as a Rust array constant
+    #[arg(long, group = "layout")]
+    rust: bool,
+
+    /// output the bytes as a Python bytes literal
+    #[arg(long, group = "layout")]
+    python: bool,
+
+    /// output the bytes as base64, wrapped at this many characters (a multiple of 4)
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        num_args = 0..=1,
+        default_missing_value = "76",
+        group = "layout",
+        conflicts_with_all = ["line_width", "squeeze", "stride", "grep"]
+    )]
+    base64: Option<usize>,
+
+    /// output the dump as JSON
+    #[cfg(feature = "json")]
+    #[arg(long, group = "layout")]
+    json: bool,
+
+    /// name of the C array, Rust constant or Python variable, derived from the file path by default
+    #[arg(long, value_name = "SYMBOL")]
+    name: Option<String>,
+
+    /// output the dump as HTML
+    #[arg(long)]
+    html: bool,
+
+    /// end lines with `\r\n` instead of `\n`
+    #[arg(long)]
+    crlf: bool,
+
+    /// don't end the last line with a newline
+    #[arg(long)]
+    no_trailing_newline: bool,
+
+    /// when to color the output
+    #[arg(long, value_enum, default_value_t = ColorWhen::Auto)]
+    color: ColorWhen,
+
+    /// write the output to this file instead of standard output
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<String>,
+
+    /// compare the file side by side with another file
+    #[arg(long, value_name = "OTHER_FILE")]
+    diff: Option<String>,
+
+    /// convert a hex dump or bare hex such as `4c6f 7265` back into binary, always reading bare hex with `--plain`
+    #[arg(short, long)]
+    revert: bool,
+
+    /// print the runs of printable characters in the file with their offsets instead of dumping it, like `strings`
+    #[arg(long, conflicts_with_all = ["revert", "diff"])]
+    strings: bool,
+
+    /// minimum number of printable characters in a row printed by `--strings`
+    #[arg(long, value_name = "LENGTH", default_value_t = 4, requires = "strings")]
+    min_length: usize,
+
+    /// end the strings printed by `--strings` that are followed by a null byte with `\0`
+    #[arg(long, requires = "strings")]
+    include_null: bool,
+
+    /// keep dumping the bytes appended to the file as it grows, like `tail -f`
+    #[arg(short, long, conflicts_with_all = ["revert", "diff"])]
+    follow: bool,
+
+    /// memory-map input files instead of reading them, which requires that they aren't truncated while being dumped
+    #[cfg(feature = "memmap2")]
+    #[arg(long, conflicts_with = "follow")]
+    mmap: bool,
+}
+
+/// Where the output is written, which is standard output unless `--output` names a file
+enum Output {
+    Stdout(io::Stdout),
+    File(Box<dyn Write>),
+}
+
+impl Output {
+    /// Create the file named by `--output` if there is one, buffering it by line when following so that the lines
+    /// dumped so far are written out while waiting
+    fn open(args: &Args) -> io::Result<Output> {
+        let Some(path) = &args.output else {
+            return Ok(Output::Stdout(io::stdout()));
+        };
+
+        let file = File::create(path)?;
+        Ok(Output::File(match args.follow {
+            true => Box::new(LineWriter::new(file)),
+            false => Box::new(BufWriter::new(file)),
+        }))
+    }
+
+    /// Whether `--color auto` should color the output
+    fn is_terminal(&self) -> bool {
+        match self {
+            Output::Stdout(stdout) => stdout.is_terminal(),
+            Output::File(_) => false,
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Stdout(stdout) => stdout.write(buf),
+            Output::File(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Stdout(stdout) => stdout.flush(),
+            Output::File(file) => file.flush(),
+        }
+    }
+}
+
+/// Exit with an error if writing the output failed
+fn check_output(result: io::Result<()>, args: &Args) {
+    if let Err(err) = result {
+        let path = args.output.as_deref().unwrap_or("stdout");
+        eprintln!("error: could not write to {path}: {err}");
+        process::exit(1);
+    }
+}
+
+/// Time to wait before checking whether a followed file has grown
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Reads a file like `tail -f`, waiting for more bytes to be appended instead of reaching the end of it
+struct Follow {
+    file: File,
+    file_path: String,
+    position: u64,
+    /// Whether the reader has already said it is waiting for the file to grow since it was last read from
+    waiting: bool,
+}
+
+impl Follow {
+    fn new(file: File, file_path: &str) -> Follow {
+        Follow {
+            file,
+            file_path: file_path.to_string(),
+            position: 0,
+            waiting: false,
+        }
+    }
+}
+
+impl Read for Follow {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let length = self.file.read(buf)?;
+            if length > 0 || buf.is_empty() {
+                self.position += length as u64;
+                self.waiting = false;
+                return Ok(length);
+            }
+
+            // like `tail -f`, a file that has been truncated is read again from its start, while the offsets keep
+            // counting up from the bytes dumped so far
+            if self.file.metadata()?.len() < self.position {
+                eprintln!("rxd: {}: file truncated", self.file_path);
+                self.position = self.file.seek(SeekFrom::Start(0))?;
+                continue;
+            }
+
+            // the dumper is told once that the file has stopped growing, so that it prints the incomplete line it
+            // has read so far and flushes the output before the wait
+            if !self.waiting {
+                self.waiting = true;
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            io::stdout().flush()?;
+            thread::sleep(FOLLOW_INTERVAL);
+        }
+    }
+}
+
+/// Parse a number of bytes, either as `0x`-prefixed hex or as decimal with an optional `k`, `m` or `g` suffix for
+/// powers of 1000 or `ki`, `mi` or `gi` for powers of 1024 (in any case), e.g. `0x1000`, `4k` or `2Mi`
+fn parse_size(s: &str) -> Result<u64, String> {
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        return u64::from_str_radix(hex, 16).map_err(|err| format!("invalid size {s:?}: {err}"));
+    }
+
+    let digits_length = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (digits, suffix) = s.split_at(digits_length);
+    let multiplier: u64 = match suffix.to_ascii_lowercase().as_str() {
+        "" => 1,
+        "k" => 1000,
+        "m" => 1_000_000,
+        "g" => 1_000_000_000,
+        "ki" => 1 << 10,
+        "mi" => 1 << 20,
+        "gi" => 1 << 30,
+        _ => return Err(format!("invalid size {s:?}: unknown suffix {suffix:?}")),
+    };
+    let number: u64 = digits
+        .parse()
+        .map_err(|err| format!("invalid size {s:?}: {err}"))?;
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("invalid size {s:?}: too large"))
+}
+
+/// Parse a number of bytes per line or per group, which must be between 1 and 256
+fn parse_byte_length(s: &str) -> Result<usize, String> {
+    let length = s
+        .parse()
+        .map_err(|err| format!("invalid length {s:?}: {err}"))?;
+    match length {
+        1..=256 => Ok(length),
+        _ => Err(format!("length must be between 1 and 256, got {length}")),
+    }
+}
+
+/// Parse a `START:END` range, where each bound is a size as accepted by [`parse_size`]
+fn parse_range(s: &str) -> Result<(u64, u64), String> {
+    let (start, end) = s
+        .split_once(':')
+        .ok_or_else(|| "expected START:END".to_string())?;
+    Ok((parse_size(start)?, parse_size(end)?))
+}
+
+/// Parse a `START:END=LABEL` annotation, where the range is as accepted by [`parse_range`]
+fn parse_annotation(s: &str) -> Result<(Range<u64>, String), String> {
+    let (range, label) = s
+        .split_once('=')
+        .ok_or_else(|| "expected START:END=LABEL".to_string())?;
+    let (start, end) = parse_range(range)?;
+    Ok((start..end, label.to_string()))
 }
 
 fn main() {
     let args = Args::parse();
 
-    let file_path = args.file_path;
-    let file = File::open(&file_path).unwrap_or_else(|err| {
-        println!("error: could not read file {file_path}: {err}");
+    let file_paths = match args.file_paths.is_empty() {
+        true => vec!["-".to_string()],
+        false => args.file_paths.clone(),
+    };
+    let show_banners = file_paths.len() > 1 && !args.revert;
+    if args.follow && file_paths.len() > 1 {
+        eprintln!("error: only a single file can be followed");
+        process::exit(1);
+    }
+
+    let mut output = Output::open(&args).unwrap_or_else(|err| {
+        let path = args.output.as_deref().unwrap_or_default();
+        eprintln!("error: could not create file {path}: {err}");
         process::exit(1);
     });
-    let reader = BufReader::new(file);
-    Dumper::new(reader)
+
+    let config = dumper_config(&args);
+    let mut base_address = 0;
+    for file_path in &file_paths {
+        if show_banners {
+            check_output(writeln!(output, "====> {file_path} <===="), &args);
+        }
+
+        let length = match file_path.as_str() {
+            // stdin can only be measured by reading all of it
+            "-" if args.continuous => {
+                let mut bytes = Vec::new();
+                io::stdin().read_to_end(&mut bytes).unwrap_or_else(|err| {
+                    eprintln!("error: could not read stdin: {err}");
+                    process::exit(1);
+                });
+                let length = bytes.len() as u64;
+                run(
+                    Cursor::new(bytes),
+                    DumperConfig::build_seekable,
+                    Dumper::dump,
+                    "stdin",
+                    base_address,
+                    &config,
+                    &mut output,
+                    &args,
+                );
+                length
+            }
+            // stdin is already followed until it is closed
+            "-" => {
+                let reader = BufReader::new(io::stdin().lock());
+                run(
+                    reader,
+                    DumperConfig::build,
+                    Dumper::dump,
+                    "stdin",
+                    base_address,
+                    &config,
+                    &mut output,
+                    &args,
+                );
+                0
+            }
+            _ => {
+                let file = File::open(file_path).unwrap_or_else(|err| {
+                    eprintln!("error: could not read file {file_path}: {err}");
+                    process::exit(1);
+                });
+                let length = file.metadata().map_or(0, |metadata| metadata.len());
+                if args.follow {
+                    run(
+                        Follow::new(file, file_path),
+                        DumperConfig::build,
+                        Dumper::dump,
+                        file_path,
+                        base_address,
+                        &config,
+                        &mut output,
+                        &args,
+                    );
+                    continue;
+                }
+                #[cfg(feature = "memmap2")]
+                if args.mmap
+                    && run_mapped(&file, file_path, base_address, &config, &mut output, &args)
+                {
+                    if args.continuous {
+                        base_address += length;
+                    }
+                    continue;
+                }
+                run(
+                    BufReader::new(file),
+                    DumperConfig::build_seekable,
+                    Dumper::dump,
+                    file_path,
+                    base_address,
+                    &config,
+                    &mut output,
+                    &args,
+                );
+                length
+            }
+        };
+
+        if args.continuous {
+            base_address += length;
+        }
+    }
+
+    check_output(output.flush(), &args);
+}
+
+/// The options of the dumps requested by `args`, which are the same for every input file
+fn dumper_config(args: &Args) -> DumperConfig {
+    let mut config = DumperConfig::new()
         .line_count(args.line_count)
-        .line_width(args.line_width)
-        .byte_group_length(args.byte_group_length)
+        .byte_count(args.byte_count)
+        .stride(args.stride)
+        .squeeze(args.squeeze && !args.verbose)
+        .squeeze_count(args.squeeze_count)
+        .block_spacing(args.block_spacing)
+        .summary(args.summary)
+        .histogram(args.histogram)
+        .group_gap(args.group_gap)
+        .line_numbers(args.line_numbers)
+        .offset_both(args.offset_both)
+        .offset_width(args.offset_width)
+        .header(!args.no_header)
+        .show_offset(!args.no_offset)
+        .show_ascii(!args.no_ascii)
+        .ascii_width(args.ascii_width)
+        .short_line_filler(args.short_line_filler.as_deref())
+        .compact(args.compact)
+        .trailing_newline(!args.no_trailing_newline)
+        .line_terminator(match args.crlf {
+            true => LineTerminator::CrLf,
+            false => LineTerminator::Lf,
+        })
+        .radix(match (args.bits, args.decimal, args.signed) {
+            (true, _, _) => Radix::Binary,
+            (_, _, true) => Radix::SignedDecimal,
+            (_, true, _) => Radix::Decimal,
+            _ => Radix::Hex,
+        })
+        .reverse_line(args.reverse_line)
+        .endian(match args.little_endian {
+            true => Endian::Little,
+            false => Endian::Big,
+        })
+        .entropy(args.entropy)
+        .line_checksum(args.line_checksum.map(ChecksumKind::from))
         .control_pictures(args.control_pictures)
-        .start_offset(args.start_offset)
-        .dump();
+        .text_encoding(args.encoding.into())
+        .text_mode(match (args.escapes, args.utf8) {
+            (true, _) => TextMode::Escapes,
+            (_, true) => TextMode::Utf8,
+            _ => TextMode::Characters,
+        })
+        .uppercase(args.uppercase)
+        .color_choice(args.color.into())
+        .html(args.html)
+        .skip(args.skip)
+        .relative_offsets(args.relative_offsets)
+        .float_precision(args.float_precision)
+        .try_byte_group_length(args.byte_group_length)
+        .and_then(|config| config.try_decode_as(args.decode.map(IntWidth::from)))
+        .and_then(|config| config.try_decode_float(args.decode_float.map(FloatWidth::from)))
+        .and_then(|config| match args.range {
+            Some((start, end)) => config.try_range(start, end),
+            None => Ok(config),
+        })
+        .and_then(|config| config.try_placeholder(args.placeholder))
+        .and_then(|config| match args.line_width {
+            LineWidth::Auto => Ok(config.line_width_auto(true)),
+            LineWidth::Bytes(line_width) => config.try_line_width(line_width),
+        })
+        .unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            process::exit(1);
+        });
+
+    if let Some(needle) = &args.grep {
+        config = config.grep(needle.as_bytes().to_vec());
+    }
+
+    if !args.annotate.is_empty() {
+        config = config.annotate(args.annotate.clone());
+    }
+
+    if let Some(name) = &args.region {
+        config = config.try_region(name).unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            process::exit(1);
+        });
+    }
+
+    if let Some(pattern) = &args.highlight {
+        config = config.highlight(pattern.as_bytes().to_vec());
+    }
+
+    config
+}
+
+/// Memory-map `file` and dump it like [`run`], returning `false` without dumping anything if it can't be mapped
+#[cfg(feature = "memmap2")]
+fn run_mapped(
+    file: &File,
+    file_path: &str,
+    base_address: u64,
+    config: &DumperConfig,
+    output: &mut Output,
+    args: &Args,
+) -> bool {
+    // SAFETY: the map is only read from, but another process truncating the file while it is mapped would crash
+    // rxd, which the documentation of `--mmap` warns about
+    let Ok(map) = (unsafe { memmap2::Mmap::map(file) }) else {
+        return false;
+    };
+
+    #[cfg(feature = "rayon")]
+    let dump = Dumper::dump_parallel;
+    #[cfg(not(feature = "rayon"))]
+    let dump = Dumper::dump;
+    run(
+        Cursor::new(&map[..]),
+        DumperConfig::build_seekable,
+        dump,
+        file_path,
+        base_address,
+        config,
+        output,
+        args,
+    );
+    true
+}
+
+/// Dump, revert or diff `reader` into `output` as requested by `args`, where `file_path` names the input in
+/// messages, printing the dump with `dump` when writing to stdout
+#[allow(clippy::too_many_arguments)]
+fn run<R: Read>(
+    reader: R,
+    new_dumper: fn(&DumperConfig, R) -> Dumper<R>,
+    dump: fn(&mut Dumper<R>) -> io::Result<()>,
+    file_path: &str,
+    base_address: u64,
+    config: &DumperConfig,
+    output: &mut Output,
+    args: &Args,
+) {
+    if args.revert {
+        let result = match args.plain {
+            true => undump_plain(reader, output),
+            false => undump(reader, output),
+        };
+        result.unwrap_or_else(|err| {
+            eprintln!("error: could not revert file {file_path}: {err}");
+            process::exit(1);
+        });
+        return;
+    }
+
+    if args.strings {
+        if args.min_length == 0 {
+            eprintln!("error: minimum string length must be at least 1");
+            process::exit(1);
+        }
+        let opts = StringsOptions::new()
+            .min_length(args.min_length)
+            .include_null(args.include_null);
+        extract_strings(reader, output, opts).unwrap_or_else(|err| {
+            eprintln!("error: could not read file {file_path}: {err}");
+            process::exit(1);
+        });
+        return;
+    }
+
+    if let Some(other_path) = &args.diff {
+        let other_file = File::open(other_path).unwrap_or_else(|err| {
+            eprintln!("error: could not read file {other_path}: {err}");
+            process::exit(1);
+        });
+        // stdin can't be measured up front, so only the other file's length is known then
+        let input_length = [
+            other_file.metadata().ok(),
+            (file_path != "stdin")
+                .then(|| fs::metadata(file_path).ok())
+                .flatten(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|metadata| metadata.len())
+        .max()
+        .unwrap_or_default();
+        let opts = DiffOptions::new()
+            .try_line_width(match args.line_width {
+                LineWidth::Auto => 16,
+                LineWidth::Bytes(line_width) => line_width,
+            })
+            .unwrap_or_else(|err| {
+                eprintln!("error: {err}");
+                process::exit(1);
+            })
+            .input_length(input_length)
+            .squeeze(args.squeeze && !args.verbose)
+            .color(ColorChoice::from(args.color).enabled(output.is_terminal()))
+            .uppercase(args.uppercase);
+        diff(reader, BufReader::new(other_file), output, opts).unwrap_or_else(|err| {
+            eprintln!("error: could not diff files {file_path} and {other_path}: {err}");
+            process::exit(1);
+        });
+        return;
+    }
+
+    let mut dumper = new_dumper(config, reader).base_address(base_address);
+
+    if args.plain {
+        let renderer = PlainRenderer::new().uppercase(args.uppercase);
+        dumper = dumper.with_renderer(Box::new(renderer));
+    } else if args.include {
+        let name = args.name.as_deref().unwrap_or(file_path);
+        let renderer = CIncludeRenderer::new(name).uppercase(args.uppercase);
+        dumper = dumper.with_renderer(Box::new(renderer));
+    } else if args.rust {
+        let name = args
+            .name
+            .clone()
+            .unwrap_or_else(|| file_path.to_uppercase());
+        let renderer = RustArrayRenderer::new(&name).uppercase(args.uppercase);
+        dumper = dumper.with_renderer(Box::new(renderer));
+    } else if let Some(columns) = args.base64 {
+        // each line of the dump is encoded on its own, so it must hold a whole number of 3-byte groups
+        if columns == 0 || !columns.is_multiple_of(4) || columns / 4 * 3 > 256 {
+            eprintln!("error: base64 line width must be a multiple of 4 up to 340, got {columns}");
+            process::exit(1);
+        }
+        dumper = dumper
+            .line_width_auto(false)
+            .line_width(columns / 4 * 3)
+            .with_renderer(Box::new(Base64Renderer::new()));
+    } else if args.python {
+        let name = args.name.as_deref().unwrap_or(file_path);
+        let renderer = PythonBytesRenderer::new(name).uppercase(args.uppercase);
+        dumper = dumper.with_renderer(Box::new(renderer));
+    }
+
+    #[cfg(feature = "json")]
+    if args.json {
+        dumper = dumper.with_renderer(Box::new(rxd::JsonRenderer::new()));
+    }
+
+    // `Dumper::dump` detects whether stdout is a terminal, while `Dumper::dump_to` never colors a file with
+    // `--color auto`
+    let result = match output {
+        Output::Stdout(_) => dump(&mut dumper),
+        Output::File(file) => dumper.dump_to(file),
+    };
+    result.unwrap_or_else(|err| {
+        eprintln!("error: could not dump file {file_path}: {err}");
+        process::exit(1);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(Ok(4096), parse_size("0x1000"));
+        assert_eq!(Ok(1234), parse_size("1234"));
+        assert_eq!(Ok(4000), parse_size("4k"));
+        assert_eq!(Ok(2 << 20), parse_size("2Mi"));
+        assert_eq!(Ok(3_000_000_000), parse_size("3G"));
+        assert!(parse_size("4kb").is_err());
+        assert!(parse_size("k").is_err());
+        assert!(parse_size("99999999999Gi").is_err());
+
+        assert_eq!(Ok(256), parse_byte_length("256"));
+        assert!(parse_byte_length("0").is_err());
+        assert!(parse_byte_length("257").is_err());
+        assert!(parse_byte_length("-1").is_err());
+
+        assert_eq!(Ok((0x100, 2048)), parse_range("0x100:2ki"));
+        assert_eq!(
+            Ok((0..4, "header.magic".to_string())),
+            parse_annotation("0:4=header.magic")
+        );
+    }
+
+    #[test]
+    fn one_output_layout() {
+        assert!(Args::try_parse_from(["rxd", "--rust", "file"]).is_ok());
+        assert!(Args::try_parse_from(["rxd", "-r", "-p", "file"]).is_ok());
+        assert!(Args::try_parse_from(["rxd", "-p", "-i", "file"]).is_err());
+        assert!(Args::try_parse_from(["rxd", "--base64", "--python", "file"]).is_err());
+        assert!(Args::try_parse_from(["rxd", "--base64", "-w", "auto", "file"]).is_err());
+        assert!(Args::try_parse_from(["rxd", "--base64", "--stride", "2", "file"]).is_err());
+    }
+
+    #[test]
+    fn follow_appended_bytes() {
+        let path = std::env::temp_dir().join(format!("rxd-follow-{}", process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        let mut follow = Follow::new(File::open(&path).unwrap(), "file");
+
+        let mut buf = [0; 8];
+        assert_eq!(3, follow.read(&mut buf).unwrap());
+        let err = follow.read(&mut buf).unwrap_err();
+        assert_eq!(io::ErrorKind::WouldBlock, err.kind());
+
+        let writer_path = path.clone();
+        let writer = thread::spawn(move || {
+            thread::sleep(FOLLOW_INTERVAL * 2);
+            let mut file = File::options().append(true).open(&writer_path).unwrap();
+            file.write_all(b"de").unwrap();
+        });
+        assert_eq!(2, follow.read(&mut buf).unwrap());
+        assert_eq!(b"de", &buf[..2]);
+        writer.join().unwrap();
+
+        std::fs::write(&path, b"x").unwrap();
+        assert_eq!(1, follow.read(&mut buf).unwrap());
+        assert_eq!(b"x", &buf[..1]);
+        std::fs::remove_file(&path).unwrap();
+    }
 }
@@ -0,0 +1,45 @@
+use alloc::string::String;
+
+use super::LineRenderer;
+
+/// Characters of the standard base64 alphabet, indexed by the 6-bit value they encode
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A layout that encodes the bytes as standard base64 (RFC 4648), one line of the dump per line of output
+///
+/// Every line is encoded on its own, so the line width of the [`Dumper`](crate::Dumper) should be a multiple of 3
+/// for the lines to join into a single base64 string, with padding only at the end of the last line. Lines of 57
+/// bytes give the usual 76 characters per line.
+#[derive(Default)]
+pub struct Base64Renderer;
+
+impl Base64Renderer {
+    /// Construct a new instance of [`Base64Renderer`]
+    pub fn new() -> Base64Renderer {
+        Base64Renderer
+    }
+}
+
+impl LineRenderer for Base64Renderer {
+    fn render_line(&self, _offset: u64, bytes: &[u8]) -> String {
+        let mut line = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let value = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |value, (index, &byte)| {
+                    value | (byte as u32) << (16 - 8 * index)
+                });
+            // a chunk of n bytes is encoded by n + 1 characters, followed by padding up to 4
+            for index in 0..4 {
+                match index <= chunk.len() {
+                    true => {
+                        line.push(ALPHABET[(value >> (18 - 6 * index) & 0x3f) as usize] as char)
+                    }
+                    false => line.push('='),
+                }
+            }
+        }
+        line
+    }
+}
@@ -0,0 +1,54 @@
+use alloc::{format, string::String};
+
+use super::{identifier, push_hex_byte, LineRenderer};
+
+/// A layout that declares the bytes as a C array, like `xxd -i`
+pub struct CIncludeRenderer {
+    name: String,
+    uppercase: bool,
+}
+
+impl CIncludeRenderer {
+    /// Construct a new instance of [`CIncludeRenderer`] declaring an array called `name`
+    ///
+    /// Characters that aren't valid in a C identifier are replaced with `_`, so a file path can be passed directly.
+    pub fn new(name: &str) -> CIncludeRenderer {
+        CIncludeRenderer {
+            name: identifier(name),
+            uppercase: false,
+        }
+    }
+
+    /// Set whether hex digits should be printed in uppercase
+    pub fn uppercase(mut self, uppercase: bool) -> CIncludeRenderer {
+        self.uppercase = uppercase;
+        self
+    }
+}
+
+impl LineRenderer for CIncludeRenderer {
+    fn render_line(&self, _offset: u64, bytes: &[u8]) -> String {
+        let mut line = String::with_capacity(2 + 6 * bytes.len());
+        line.push_str("  ");
+        for (index, &byte) in bytes.iter().enumerate() {
+            if index > 0 {
+                line.push_str(", ");
+            }
+            line.push_str("0x");
+            push_hex_byte(&mut line, byte, self.uppercase);
+        }
+        line
+    }
+
+    fn header(&self) -> Option<String> {
+        Some(format!("unsigned char {}[] = {{", self.name))
+    }
+
+    fn footer(&self, length: u64) -> Option<String> {
+        Some(format!("}};\nunsigned int {}_len = {length};", self.name))
+    }
+
+    fn line_delimiter(&self) -> &str {
+        ","
+    }
+}
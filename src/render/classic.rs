@@ -0,0 +1,861 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+use core::{fmt::Write, iter, ops::Range};
+
+use unicode_width::UnicodeWidthChar;
+
+use super::{
+    push_hex_byte, ChecksumKind, Endian, FloatWidth, IntWidth, Layout, LineRenderer, OffsetBase,
+    Radix, SeparatorStyle, TextMode,
+};
+use crate::{color, ByteStyle, TextEncoding};
+
+/// The default layout, made of an offset column, a hex column and an ASCII column
+#[derive(Clone)]
+pub struct ClassicRenderer {
+    pub(crate) control_pictures: bool,
+    pub(crate) show_ascii: bool,
+    /// Maximum number of bytes of each line shown in the text column, which shows all of them if unset
+    pub(crate) ascii_width: Option<usize>,
+    pub(crate) text_encoding: TextEncoding,
+    /// Maps each byte to the character shown for it in the text column, replacing the encoding if set
+    pub(crate) text_renderer: Option<Arc<dyn Fn(u8) -> char + Send + Sync>>,
+    pub(crate) text_mode: TextMode,
+    pub(crate) placeholder: char,
+    /// Whether each byte of the line being rendered is part of a highlighted match
+    pub(crate) highlighted: Vec<bool>,
+    pub(crate) uppercase: bool,
+    pub(crate) color: bool,
+    /// Chooses the colors of each byte from its offset and value when the output is colored, replacing the colors
+    /// of the byte categories if set
+    pub(crate) style_fn: Option<Arc<dyn Fn(u64, u8) -> ByteStyle + Send + Sync>>,
+    pub(crate) html: bool,
+    pub(crate) line_numbers: bool,
+    pub(crate) line_number_width: usize,
+    /// 1-based number of the line being rendered
+    pub(crate) line_number: u64,
+    pub(crate) show_offset: bool,
+    /// Whether the row of byte offsets and the separator row are printed before the first line
+    pub(crate) show_header: bool,
+    pub(crate) separators: SeparatorStyle,
+    pub(crate) compact: bool,
+    pub(crate) offset_base: OffsetBase,
+    /// Number of characters in the offset column
+    pub(crate) offset_width: usize,
+    /// Number of digits of every offset, replacing the number fitted to the offsets of the dump if set
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fixed_offset_width: Option<usize>,
+    /// Whether each offset is followed by its decimal value in parentheses, e.g. `00000010 (16)`
+    pub(crate) offset_both: bool,
+    /// Number of digits of the offset in the offset base, which is less than the width of the column if the decimal
+    /// value follows it
+    pub(crate) offset_digits: usize,
+    /// Formats each offset, replacing the offset base if set
+    pub(crate) offset_formatter: Option<Arc<dyn Fn(u64) -> String + Send + Sync>>,
+    pub(crate) line_width: usize,
+    pub(crate) byte_group_length: usize,
+    /// Number of bytes after which an extra space is inserted between groups
+    pub(crate) group_gap: Option<usize>,
+    pub(crate) endian: Endian,
+    /// Whether the bytes of each line are shown in reverse order in the data and text columns
+    pub(crate) reverse_line: bool,
+    pub(crate) radix: Radix,
+    /// Text shown in the data column in place of each byte missing from a line shorter than the line width
+    pub(crate) short_line_filler: Option<String>,
+    pub(crate) decode_as: Option<IntWidth>,
+    pub(crate) decode_float: Option<FloatWidth>,
+    /// Number of digits printed after the decimal point of each decoded float
+    pub(crate) float_precision: usize,
+    pub(crate) entropy: bool,
+    pub(crate) line_checksum: Option<ChecksumKind>,
+    /// Labels of the annotations overlapping the line being rendered
+    pub(crate) annotation: Option<String>,
+}
+
+/// Number of characters in the entropy column, e.g. `7.98`
+const ENTROPY_LENGTH: usize = 4;
+
+/// [Shannon entropy] of `bytes` in bits per byte, computed from their histogram
+///
+/// [Shannon entropy]: https://en.wikipedia.org/wiki/Entropy_(information_theory)
+fn entropy(bytes: &[u8]) -> f64 {
+    let mut counts = [0usize; 256];
+    for &byte in bytes {
+        counts[byte as usize] += 1;
+    }
+
+    let length = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let probability = count as f64 / length;
+            probability * libm::log2(1.0 / probability)
+        })
+        .sum()
+}
+
+impl Default for ClassicRenderer {
+    fn default() -> Self {
+        ClassicRenderer {
+            control_pictures: false,
+            show_ascii: true,
+            ascii_width: None,
+            text_encoding: TextEncoding::Ascii,
+            text_renderer: None,
+            text_mode: TextMode::Characters,
+            placeholder: '.',
+            highlighted: Vec::new(),
+            uppercase: false,
+            color: false,
+            style_fn: None,
+            html: false,
+            line_numbers: false,
+            line_number_width: 6,
+            line_number: 1,
+            show_offset: true,
+            show_header: true,
+            separators: SeparatorStyle::classic(),
+            compact: false,
+            offset_base: OffsetBase::Hex,
+            offset_width: 8,
+            fixed_offset_width: None,
+            offset_both: false,
+            offset_digits: 8,
+            offset_formatter: None,
+            line_width: 0x10,
+            byte_group_length: 1,
+            group_gap: None,
+            endian: Endian::Big,
+            reverse_line: false,
+            radix: Radix::Hex,
+            short_line_filler: None,
+            decode_as: None,
+            decode_float: None,
+            float_precision: 5,
+            entropy: false,
+            line_checksum: None,
+            annotation: None,
+        }
+    }
+}
+
+impl ClassicRenderer {
+    /// Construct a new instance of [`ClassicRenderer`], for use with [`render_lines`](crate::render_lines)
+    ///
+    /// The layout of a [`Dumper`](crate::Dumper) is configured through the dumper's own options instead.
+    pub fn new() -> ClassicRenderer {
+        ClassicRenderer::default()
+    }
+
+    /// Set the number of bytes per line
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line_width` is 0
+    pub fn line_width(mut self, line_width: usize) -> ClassicRenderer {
+        assert!(line_width > 0, "line width must be at least 1");
+        self.line_width = line_width;
+        self
+    }
+
+    /// Set the number of bytes grouped together per line, which is clamped to the line width
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte_group_length` is 0
+    pub fn byte_group_length(mut self, byte_group_length: usize) -> ClassicRenderer {
+        assert!(
+            byte_group_length > 0,
+            "byte group length must be at least 1"
+        );
+        self.byte_group_length = byte_group_length;
+        self
+    }
+
+    /// Set whether hex digits should be printed in uppercase
+    pub fn uppercase(mut self, uppercase: bool) -> ClassicRenderer {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// The position and width of each column of a full line, in characters
+    pub fn layout(&self) -> Layout {
+        let (offset_separator, separator) = self.column_separators();
+        let offset_separator = offset_separator.chars().count();
+        let separator = separator.chars().count();
+
+        let mut column = 0;
+        if self.line_numbers {
+            column += self.line_number_width + offset_separator;
+        }
+        let offset_width = match self.show_offset {
+            true => self.offset_width,
+            false => 0,
+        };
+        if self.show_offset {
+            column += offset_width + offset_separator;
+        }
+
+        let hex_column_start = column;
+        let hex_column_width = self.get_line_hex_pad_length();
+        column += hex_column_width;
+
+        let mut ascii_column_start = None;
+        if self.show_ascii {
+            column += separator;
+            ascii_column_start = Some(column);
+            column += self.get_text_bytes() * self.text_mode.cell_width();
+        }
+        if let Some(int_width) = self.decode_as {
+            column += separator + self.get_line_decimal_length(int_width);
+        }
+        if let Some(float_width) = self.decode_float {
+            column += separator + self.get_line_float_length(float_width);
+        }
+        if self.entropy {
+            column += separator + ENTROPY_LENGTH;
+        }
+        if let Some(kind) = self.line_checksum {
+            column += separator + kind.label().len();
+        }
+
+        Layout {
+            offset_width,
+            hex_column_start,
+            hex_column_width,
+            ascii_column_start,
+            total_width: column,
+        }
+    }
+
+    /// Number of bytes in each full group, which is never more than the line width
+    fn group_length(&self) -> usize {
+        self.byte_group_length.min(self.line_width)
+    }
+
+    /// Whether an extra space goes before the group starting at the byte at `index` in the line
+    fn has_gap_before(&self, index: usize) -> bool {
+        match self.group_gap {
+            Some(every) => index > 0 && index.is_multiple_of(every),
+            None => false,
+        }
+    }
+
+    /// Number of extra spaces between the groups of the first `length` bytes of a line
+    fn get_gap_count(&self, length: usize) -> usize {
+        (0..length)
+            .step_by(self.group_length())
+            .filter(|&index| self.has_gap_before(index))
+            .count()
+    }
+
+    /// Number of visible characters in the data column for `length` bytes
+    fn get_hex_length(&self, length: usize) -> usize {
+        // the groups are separated by one space fewer than there are groups, and there are none without bytes
+        (length * self.radix.byte_width() + length.div_ceil(self.group_length())).saturating_sub(1)
+            + self.get_gap_count(length)
+    }
+
+    fn get_line_hex_pad_length(&self) -> usize {
+        self.get_hex_length(self.line_width)
+    }
+
+    /// Format the row labelling each group of the data column with the index of its first byte within the line
+    ///
+    /// Each label starts in the same column as the first byte of its group in the body, and the row is padded to
+    /// `hex_pad_length` characters. A label that would run into the previous one is left out.
+    fn format_byte_offsets(&self, hex_pad_length: usize) -> String {
+        let label_width = format!("{:x}", self.line_width.saturating_sub(1))
+            .len()
+            .max(2);
+        let group_width = self.group_length() * self.radix.byte_width() + 1;
+
+        let mut row = String::new();
+        for (group, index) in (0..self.line_width)
+            .step_by(self.group_length())
+            .enumerate()
+        {
+            let column = group * group_width + self.get_gap_count(index + 1);
+            if group > 0 && row.len() >= column {
+                continue;
+            }
+            row += &" ".repeat(column - row.len());
+            // a reversed line starts with its last byte
+            let index = match self.reverse_line {
+                true => self.line_width - 1 - index,
+                false => index,
+            };
+            row += &match self.uppercase {
+                true => format!("{index:0label_width$X}"),
+                false => format!("{index:0label_width$x}"),
+            };
+        }
+
+        format!("{row:<hex_pad_length$}")
+    }
+
+    /// Set the line width to the largest one whose lines fit in `columns` characters, preferring multiples of the
+    /// byte group length so that every group is full
+    pub(crate) fn fit_line_width(&mut self, columns: usize) {
+        let mut fallback = None;
+        for line_width in (1..=256).rev() {
+            self.line_width = line_width;
+            if self.layout().total_width > columns {
+                continue;
+            }
+            if line_width % self.byte_group_length == 0 {
+                return;
+            }
+            fallback.get_or_insert(line_width);
+        }
+        self.line_width = fallback.unwrap_or(1);
+    }
+
+    fn write_byte(&self, out: &mut String, byte: u8) {
+        let _ = match self.radix {
+            Radix::Hex => {
+                push_hex_byte(out, byte, self.uppercase);
+                Ok(())
+            }
+            Radix::Octal => write!(out, "{byte:03o}"),
+            Radix::Binary => write!(out, "{byte:08b}"),
+            Radix::Decimal => write!(out, "{byte:03}"),
+            Radix::SignedDecimal => write!(out, "{:+04}", byte as i8),
+        };
+    }
+
+    /// Separators after the offset column and between the later columns, which are single spaces in compact mode
+    fn column_separators(&self) -> (&str, &str) {
+        match self.compact {
+            true => (" ", " "),
+            false => (&self.separators.offset, &self.separators.text),
+        }
+    }
+
+    /// Number of bytes of a full line shown in the text column
+    fn get_text_bytes(&self) -> usize {
+        self.ascii_width.map_or(self.line_width, |ascii_width| {
+            ascii_width.min(self.line_width)
+        })
+    }
+
+    /// Set the width of the offset column to fit the offsets from `first_offset` to `last_offset`
+    #[cfg(feature = "std")]
+    pub(crate) fn fit_offset_width(&mut self, first_offset: u64, last_offset: u64) {
+        self.offset_width = match &self.offset_formatter {
+            Some(offset_formatter) => [first_offset, last_offset]
+                .map(|offset| offset_formatter(offset).chars().count())
+                .into_iter()
+                .max()
+                .unwrap_or_default(),
+            None => self
+                .fixed_offset_width
+                .unwrap_or_else(|| self.offset_base.width(last_offset)),
+        };
+        self.offset_digits = self.offset_width;
+        if self.offset_both && self.offset_formatter.is_none() {
+            // ` (` and `)` around the decimal value, which is widest for the last offset
+            self.offset_width += 3 + last_offset.to_string().len();
+        }
+    }
+
+    /// Whether `offset` fits in the number of digits the offset column was fixed to, if it was
+    #[cfg(feature = "std")]
+    pub(crate) fn offset_fits(&self, offset: u64) -> bool {
+        let Some(width) = self
+            .fixed_offset_width
+            .filter(|_| self.offset_formatter.is_none())
+        else {
+            return true;
+        };
+        let digits = match self.offset_base {
+            OffsetBase::Hex => format!("{offset:x}").len(),
+            OffsetBase::Decimal => offset.to_string().len(),
+            OffsetBase::Octal => format!("{offset:o}").len(),
+        };
+        digits <= width
+    }
+
+    fn write_offset(&self, out: &mut String, offset: u64) {
+        let width = self.offset_digits;
+        let start = out.len();
+        let _ = match (&self.offset_formatter, self.offset_base, self.uppercase) {
+            (Some(offset_formatter), _, _) => {
+                let offset = format!("{:>width$}", offset_formatter(offset));
+                match self.html {
+                    true => write!(out, "{}", color::html_escape(&offset)),
+                    false => write!(out, "{offset}"),
+                }
+            }
+            (None, OffsetBase::Hex, true) => write!(out, "{offset:0width$X}"),
+            (None, OffsetBase::Hex, false) => write!(out, "{offset:0width$x}"),
+            (None, OffsetBase::Decimal, _) => write!(out, "{offset:0width$}"),
+            (None, OffsetBase::Octal, _) => write!(out, "{offset:0width$o}"),
+        };
+        if self.offset_both && self.offset_formatter.is_none() {
+            let _ = write!(out, " ({offset})");
+            push_spaces(out, self.offset_width.saturating_sub(out.len() - start));
+        }
+    }
+
+    /// Number of characters in the decimal column when a line is full
+    fn get_line_decimal_length(&self, int_width: IntWidth) -> usize {
+        let groups = self.line_width / int_width.bytes();
+        (groups * (int_width.digits() + 1)).saturating_sub(1)
+    }
+
+    /// Number of characters each decoded float takes up, which fits a sign, six integer digits, the decimal point and
+    /// the fraction, or the same number in scientific notation
+    fn get_float_cell_length(&self) -> usize {
+        self.float_precision + 8
+    }
+
+    fn get_line_float_length(&self, float_width: FloatWidth) -> usize {
+        let groups = self.line_width / float_width.bytes();
+        (groups * (self.get_float_cell_length() + 1)).saturating_sub(1)
+    }
+
+    /// Format every complete group of `line_bytes` as a right-aligned float, switching to scientific notation for
+    /// numbers too large to fit
+    fn format_float(&self, float_width: FloatWidth, line_bytes: &[u8]) -> String {
+        let precision = self.float_precision;
+        let cell_length = self.get_float_cell_length();
+        line_bytes
+            .chunks_exact(float_width.bytes())
+            .map(|chunk| {
+                let value = match (float_width, self.endian) {
+                    (FloatWidth::F32, Endian::Big) => {
+                        f32::from_be_bytes(chunk.try_into().unwrap()) as f64
+                    }
+                    (FloatWidth::F32, Endian::Little) => {
+                        f32::from_le_bytes(chunk.try_into().unwrap()) as f64
+                    }
+                    (FloatWidth::F64, Endian::Big) => f64::from_be_bytes(chunk.try_into().unwrap()),
+                    (FloatWidth::F64, Endian::Little) => {
+                        f64::from_le_bytes(chunk.try_into().unwrap())
+                    }
+                };
+                let text = format!("{value:.precision$}");
+                match text.len() > cell_length {
+                    true => format!("{value:>cell_length$.precision$e}"),
+                    false => format!("{text:>cell_length$}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Format every complete group of `line_bytes` as a right-aligned decimal integer
+    fn format_decimal(&self, int_width: IntWidth, line_bytes: &[u8]) -> String {
+        let digits = int_width.digits();
+        line_bytes
+            .chunks_exact(int_width.bytes())
+            .map(|chunk| {
+                let value = match self.endian {
+                    Endian::Big => chunk.iter().fold(0, |acc, &byte| acc << 8 | byte as u64),
+                    Endian::Little => chunk
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, &byte| acc << 8 | byte as u64),
+                };
+                format!("{value:>digits$}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The character shown for `byte` in the text column, which is the placeholder if the character would take up
+    /// more or less than a single column and misalign the columns after it
+    fn format_character(&self, byte: u8) -> char {
+        let character = self.decode_character(byte);
+        match character.is_ascii_graphic() || character.width() == Some(1) {
+            true => character,
+            false => self.placeholder,
+        }
+    }
+
+    fn decode_character(&self, byte: u8) -> char {
+        if let Some(text_renderer) = &self.text_renderer {
+            return text_renderer(byte);
+        }
+
+        match self.text_encoding.decode(byte) {
+            Some(character) => character,
+            None if byte < 0x20
+                && self.control_pictures
+                && self.text_encoding.is_ascii_compatible() =>
+            {
+                char::from_u32(byte as u32 + 0x2400).unwrap()
+            }
+            None if byte == 0x7f
+                && self.control_pictures
+                && self.text_encoding.is_ascii_compatible() =>
+            {
+                '\u{2421}'
+            }
+            None => self.placeholder,
+        }
+    }
+
+    /// Format the cell representing `byte` in the text column, which is [`TextMode::cell_width`] characters wide
+    fn write_text(&self, out: &mut String, byte: u8) {
+        match (self.text_mode, byte) {
+            (TextMode::Characters | TextMode::Utf8, _) => out.push(self.format_character(byte)),
+            (TextMode::Escapes, b'\n') => out.push_str("\\n"),
+            (TextMode::Escapes, b'\t') => out.push_str("\\t"),
+            (TextMode::Escapes, b'\r') => out.push_str("\\r"),
+            (TextMode::Escapes, 0x00) => out.push_str("\\0"),
+            (TextMode::Escapes, b'\\') => out.push_str("\\\\"),
+            (TextMode::Escapes, _) => {
+                out.push(' ');
+                out.push(self.format_character(byte));
+            }
+        }
+    }
+
+    /// Write the text column for `bytes` decoded as UTF-8, see [`TextMode::Utf8`]
+    fn write_utf8_text(&self, out: &mut String, bytes: &[u8], byte_offset: &impl Fn(usize) -> u64) {
+        let mut index = 0;
+        for chunk in bytes.utf8_chunks() {
+            for character in chunk.valid().chars() {
+                let length = character.len_utf8();
+                let byte = bytes[index];
+                if length == 1 {
+                    self.write_cell(out, index, byte_offset, byte, |out| {
+                        self.write_text(out, byte)
+                    });
+                } else {
+                    let (character, width) = match character.width() {
+                        Some(width) if width > 0 => (character, width),
+                        _ => (self.placeholder, 1),
+                    };
+                    let cells = index..index + length;
+                    self.write_character_cells(out, cells, byte_offset, byte, character, width);
+                }
+                index += length;
+            }
+
+            let length = chunk.invalid().len();
+            if length > 0 {
+                let byte = bytes[index];
+                let cells = index..index + length;
+                self.write_character_cells(out, cells, byte_offset, byte, '\u{fffd}', 1);
+                index += length;
+            }
+        }
+    }
+
+    /// Write `character`, which is `width` cells wide, over the first of the bytes at the indices `cells` in the
+    /// line, followed by a space for each of the rest of their cells
+    fn write_character_cells(
+        &self,
+        out: &mut String,
+        cells: Range<usize>,
+        byte_offset: &impl Fn(usize) -> u64,
+        byte: u8,
+        character: char,
+        width: usize,
+    ) {
+        self.write_cell(out, cells.start, byte_offset, byte, |out| {
+            out.push(character)
+        });
+        for index in cells.start + width..cells.end {
+            self.write_cell(out, index, byte_offset, byte, |out| out.push(' '));
+        }
+    }
+
+    /// Wrap the text representing `byte` at `offset` in either ANSI escape sequences for its style or an HTML element
+    /// depending on its category
+    fn style_byte(&self, offset: u64, byte: u8, text: &str) -> String {
+        if self.html {
+            color::html_span(byte, text)
+        } else if self.color {
+            let style = match &self.style_fn {
+                Some(style_fn) => style_fn(offset, byte),
+                None => ByteStyle::of_category(byte),
+            };
+            style.paint(text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Wrap the styled text of the byte at `index` in the line in reverse video if it is part of a highlighted match
+    fn highlight_byte(&self, index: usize, text: String) -> String {
+        match (self.highlighted.get(index), self.html) {
+            (Some(true), true) => format!("<mark>{text}</mark>"),
+            (Some(true), false) => format!("\x1b[7m{text}\x1b[27m"),
+            _ => text,
+        }
+    }
+
+    /// Write the text that `write` produces for the byte at `index` in the line, styled and highlighted as needed,
+    /// where `byte_offset` gives the offset of the byte at each index
+    fn write_cell(
+        &self,
+        out: &mut String,
+        index: usize,
+        byte_offset: &impl Fn(usize) -> u64,
+        byte: u8,
+        write: impl Fn(&mut String),
+    ) {
+        // most lines are neither styled nor highlighted, so the text is written in place when possible
+        if !self.html && !self.color && self.highlighted.get(index) != Some(&true) {
+            write(out);
+            return;
+        }
+
+        let mut text = String::new();
+        write(&mut text);
+        out.push_str(&self.highlight_byte(index, self.style_byte(byte_offset(index), byte, &text)));
+    }
+
+    /// Append a single line of the dump to `buf`, like [`LineRenderer::render_line`] but without allocating a string
+    /// for it
+    ///
+    /// `buf` isn't cleared first, so that lines can be collected into one string, or a single buffer can be reused
+    /// for every line by clearing it between calls.
+    pub fn format_line_into(&self, offset: u64, bytes: &[u8], buf: &mut String) {
+        self.write_numbered_line(buf, self.line_number, offset, bytes);
+    }
+
+    /// Append a single line of the dump to `line` like [`ClassicRenderer::format_line_into`], numbering it
+    /// `line_number` instead of the number set on the renderer
+    pub(crate) fn write_numbered_line(
+        &self,
+        line: &mut String,
+        line_number: u64,
+        chunk_offset: u64,
+        line_bytes: &[u8],
+    ) {
+        let (offset_separator, separator) = self.column_separators();
+        let start = line.len();
+
+        if self.line_numbers {
+            let width = self.line_number_width;
+            let _ = write!(line, "{line_number:>width$}{offset_separator}");
+        }
+        if self.show_offset {
+            self.write_offset(line, chunk_offset);
+            line.push_str(offset_separator);
+        }
+
+        // the later columns are computed from the bytes in the order they were read
+        let reversed_bytes: Vec<u8>;
+        let shown_bytes = match self.reverse_line {
+            true => {
+                reversed_bytes = line_bytes.iter().rev().copied().collect();
+                &reversed_bytes
+            }
+            false => line_bytes,
+        };
+        let byte_offset = |index: usize| {
+            chunk_offset
+                + match self.reverse_line {
+                    true => line_bytes.len() - 1 - index,
+                    false => index,
+                } as u64
+        };
+
+        let group_length = self.group_length();
+        for (group, chunk) in shown_bytes.chunks(group_length).enumerate() {
+            let group_index = group * group_length;
+            if group_index > 0 {
+                line.push(' ');
+            }
+            if self.has_gap_before(group_index) {
+                line.push(' ');
+            }
+
+            for position in 0..chunk.len() {
+                let position = match self.endian {
+                    Endian::Big => position,
+                    Endian::Little => chunk.len() - 1 - position,
+                };
+                let byte = chunk[position];
+                self.write_cell(line, group_index + position, &byte_offset, byte, |out| {
+                    self.write_byte(out, byte)
+                });
+            }
+        }
+
+        // escape sequences and tags take up no space on screen, so pad based on the number of visible characters, and
+        // a column is only padded when another one follows it
+        let mut padding = match self.compact {
+            true => 0,
+            false => self.get_line_hex_pad_length() - self.get_hex_length(shown_bytes.len()),
+        };
+        if let Some(filler) = self.short_line_filler.as_deref() {
+            let byte_width = self.radix.byte_width();
+            for index in shown_bytes.len()..self.line_width {
+                if index.is_multiple_of(group_length) {
+                    if index > 0 {
+                        line.push(' ');
+                    }
+                    if self.has_gap_before(index) {
+                        line.push(' ');
+                    }
+                }
+                let _ = write!(line, "{filler:<byte_width$}");
+            }
+            padding = 0;
+        }
+        if self.show_ascii {
+            push_spaces(line, padding);
+            line.push_str(separator);
+            let text_bytes = self.get_text_bytes();
+            match self.text_mode {
+                TextMode::Utf8 => {
+                    let text_bytes = shown_bytes.len().min(text_bytes);
+                    self.write_utf8_text(line, &shown_bytes[..text_bytes], &byte_offset)
+                }
+                _ => {
+                    for (index, &byte) in shown_bytes.iter().take(text_bytes).enumerate() {
+                        self.write_cell(line, index, &byte_offset, byte, |out| {
+                            self.write_text(out, byte)
+                        });
+                    }
+                }
+            }
+            if !self.compact {
+                padding =
+                    text_bytes.saturating_sub(shown_bytes.len()) * self.text_mode.cell_width();
+            }
+        }
+
+        if self.decode_as.is_none()
+            && self.decode_float.is_none()
+            && !self.entropy
+            && self.line_checksum.is_none()
+            && self.annotation.is_none()
+        {
+            return;
+        }
+
+        push_spaces(line, padding);
+        if let Some(int_width) = self.decode_as {
+            let decimal_length = self.get_line_decimal_length(int_width);
+            let line_decimal = self.format_decimal(int_width, line_bytes);
+            let _ = write!(line, "{separator}{line_decimal:<decimal_length$}");
+        }
+        if let Some(float_width) = self.decode_float {
+            let float_length = self.get_line_float_length(float_width);
+            let line_float = self.format_float(float_width, line_bytes);
+            let _ = write!(line, "{separator}{line_float:<float_length$}");
+        }
+        if self.entropy {
+            let _ = write!(line, "{separator}{:.2}", entropy(line_bytes));
+        }
+        if let Some(kind) = self.line_checksum {
+            line.push_str(separator);
+            push_hex_byte(line, kind.checksum(line_bytes), self.uppercase);
+            // the label in the header can be wider than the checksum
+            push_spaces(line, kind.label().len() - 2);
+        }
+        if let Some(annotation) = &self.annotation {
+            match self.html {
+                true => {
+                    let _ = write!(line, " ; {}", color::html_escape(annotation));
+                }
+                false => {
+                    let _ = write!(line, " ; {annotation}");
+                }
+            }
+        }
+
+        let end = start + line[start..].trim_end().len();
+        line.truncate(end);
+    }
+}
+
+/// Append `count` spaces to `out`
+fn push_spaces(out: &mut String, count: usize) {
+    out.extend(iter::repeat_n(' ', count));
+}
+
+impl LineRenderer for ClassicRenderer {
+    fn render_line(&self, chunk_offset: u64, line_bytes: &[u8]) -> String {
+        let mut line = String::with_capacity(self.offset_width + 0x10 + 4 * self.line_width);
+        self.format_line_into(chunk_offset, line_bytes, &mut line);
+        line
+    }
+
+    fn render_line_into(&self, offset: u64, bytes: &[u8], buf: &mut String) {
+        self.format_line_into(offset, bytes, buf);
+    }
+
+    fn header(&self) -> Option<String> {
+        if self.compact || !self.show_header {
+            return self.html.then(|| "<pre class=\"rxd\">".to_string());
+        }
+
+        let hex_pad_length = self.get_line_hex_pad_length();
+        let byte_offsets = self.format_byte_offsets(hex_pad_length);
+        let text_length = self.get_text_bytes() * self.text_mode.cell_width();
+        let fill = self.separators.fill.to_string();
+        let (mut byte_offsets_line, mut separator_line) = match self.show_offset {
+            true => (
+                " ".repeat(self.offset_width) + &self.separators.offset,
+                fill.repeat(self.offset_width) + &self.separators.row(&self.separators.offset),
+            ),
+            false => (String::new(), String::new()),
+        };
+        if self.line_numbers {
+            let width = self.line_number_width;
+            byte_offsets_line.insert_str(0, &(" ".repeat(width) + &self.separators.offset));
+            separator_line.insert_str(
+                0,
+                &(fill.repeat(width) + &self.separators.row(&self.separators.offset)),
+            );
+        }
+        byte_offsets_line += &byte_offsets;
+        separator_line += &fill.repeat(hex_pad_length);
+
+        // the width of each later column, along with its label in the header if it has one
+        let mut extra_columns = Vec::new();
+        if self.show_ascii {
+            extra_columns.push((text_length, ""));
+        }
+        if let Some(int_width) = self.decode_as {
+            extra_columns.push((self.get_line_decimal_length(int_width), ""));
+        }
+        if let Some(float_width) = self.decode_float {
+            extra_columns.push((self.get_line_float_length(float_width), ""));
+        }
+        if self.entropy {
+            extra_columns.push((ENTROPY_LENGTH, ""));
+        }
+        if let Some(kind) = self.line_checksum {
+            extra_columns.push((kind.label().len(), kind.label()));
+        }
+
+        let separator = &self.separators.text;
+        for &(length, label) in &extra_columns {
+            let _ = write!(byte_offsets_line, "{separator}{label:<length$}");
+            separator_line += &(self.separators.row(separator) + &fill.repeat(length));
+        }
+        if extra_columns.is_empty() {
+            byte_offsets_line.truncate(byte_offsets_line.trim_end().len());
+        }
+
+        match self.html {
+            true => Some(format!(
+                "<pre class=\"rxd\">\n{byte_offsets_line}\n{separator_line}"
+            )),
+            false => Some(format!("{byte_offsets_line}\n{separator_line}")),
+        }
+    }
+
+    fn footer(&self, _length: u64) -> Option<String> {
+        match self.html {
+            true => Some("</pre>".to_string()),
+            false => None,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+
+use super::LineRenderer;
+
+/// A layout for machine consumption, printing the dump as a JSON array with an object for each line
+///
+/// Each object has the offset of the line, its bytes as numbers, and its ASCII text (with non-printable bytes
+/// replaced by `.`), e.g. `{"offset":0,"bytes":[72,105],"ascii":"Hi"}`.
+#[derive(Default)]
+pub struct JsonRenderer;
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    offset: u64,
+    bytes: &'a [u8],
+    ascii: String,
+}
+
+impl JsonRenderer {
+    /// Construct a new instance of [`JsonRenderer`]
+    pub fn new() -> JsonRenderer {
+        JsonRenderer
+    }
+}
+
+impl LineRenderer for JsonRenderer {
+    fn render_line(&self, offset: u64, bytes: &[u8]) -> String {
+        let ascii = bytes
+            .iter()
+            .map(|&byte| match byte {
+                0x20..=0x7e => byte as char,
+                _ => '.',
+            })
+            .collect();
+
+        let line = JsonLine {
+            offset,
+            bytes,
+            ascii,
+        };
+        let line = serde_json::to_string(&line).expect("dump lines should serialize to JSON");
+
+        format!("  {line}")
+    }
+
+    fn header(&self) -> Option<String> {
+        Some("[".to_string())
+    }
+
+    fn footer(&self, _length: u64) -> Option<String> {
+        Some("]".to_string())
+    }
+
+    fn line_delimiter(&self) -> &str {
+        ","
+    }
+}
@@ -0,0 +1,474 @@
+mod base64;
+mod c_include;
+mod classic;
+#[cfg(feature = "json")]
+mod json;
+mod plain;
+mod python_bytes;
+mod rust_array;
+
+use alloc::{string::String, vec::Vec};
+
+pub use base64::Base64Renderer;
+pub use c_include::CIncludeRenderer;
+pub use classic::ClassicRenderer;
+#[cfg(feature = "json")]
+pub use json::JsonRenderer;
+pub use plain::PlainRenderer;
+pub use python_bytes::PythonBytesRenderer;
+pub use rust_array::RustArrayRenderer;
+
+/// Numeral system used to print each byte in the data column
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Radix {
+    /// Hexadecimal (base 16), e.g. `4c`
+    #[default]
+    Hex,
+    /// Octal (base 8), e.g. `114`
+    Octal,
+    /// Binary (base 2), e.g. `01001100`
+    Binary,
+    /// Decimal (base 10), e.g. `076`
+    Decimal,
+    /// Signed decimal (base 10), reading each byte as an `i8` between -128 and 127, e.g. `+076` or `-052`
+    SignedDecimal,
+}
+
+impl Radix {
+    /// Number of digits needed to print any byte
+    pub(crate) fn byte_width(self) -> usize {
+        match self {
+            Radix::Hex => 2,
+            Radix::Octal => 3,
+            Radix::Binary => 8,
+            Radix::Decimal => 3,
+            Radix::SignedDecimal => 4,
+        }
+    }
+}
+
+/// How bytes are represented in the text column
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextMode {
+    /// One character per byte, with unprintable bytes shown as the placeholder character (`.` by default)
+    #[default]
+    Characters,
+    /// Like [`od -c`], two characters per byte: `\n`, `\t`, `\r`, `\0` and `\\` are shown as C-style escapes and
+    /// every other byte as its character right-aligned after a space, which keeps the column aligned at twice its
+    /// usual width
+    ///
+    /// [`od -c`]: https://man7.org/linux/man-pages/man1/od.1.html
+    Escapes,
+    /// Bytes are decoded as UTF-8, showing each character over its first byte and padding the cells of the rest of
+    /// its bytes with spaces, so that the column stays aligned with one cell per byte
+    ///
+    /// A wide character takes up two cells, and so is followed by one fewer space. Each invalid sequence is shown as
+    /// `U+FFFD REPLACEMENT CHARACTER` in the same way, as are the parts of a character split across two lines since
+    /// each line is decoded on its own. Other characters that can't be printed are shown as the placeholder.
+    Utf8,
+}
+
+impl TextMode {
+    /// Number of characters each byte takes up in the text column
+    pub(crate) fn cell_width(self) -> usize {
+        match self {
+            TextMode::Characters | TextMode::Utf8 => 1,
+            TextMode::Escapes => 2,
+        }
+    }
+}
+
+/// Byte order used to display multi-byte groups
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endian {
+    /// Bytes are displayed in the order they appear in the input
+    #[default]
+    Big,
+    /// Bytes are displayed in reverse order within each group, e.g. `78 56 34 12` as `12345678`
+    Little,
+}
+
+/// Width of the unsigned integers decoded from each group of bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntWidth {
+    /// 16-bit integers (`u16`)
+    U16,
+    /// 32-bit integers (`u32`)
+    U32,
+    /// 64-bit integers (`u64`)
+    U64,
+}
+
+impl IntWidth {
+    /// Number of bytes in an integer of this width
+    pub fn bytes(self) -> usize {
+        match self {
+            IntWidth::U16 => 2,
+            IntWidth::U32 => 4,
+            IntWidth::U64 => 8,
+        }
+    }
+
+    /// Number of decimal digits needed to print the largest integer of this width
+    pub(crate) fn digits(self) -> usize {
+        match self {
+            IntWidth::U16 => 5,
+            IntWidth::U32 => 10,
+            IntWidth::U64 => 20,
+        }
+    }
+}
+
+/// Width of the IEEE 754 floating-point numbers decoded from each group of bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatWidth {
+    /// Single-precision floats (`f32`)
+    F32,
+    /// Double-precision floats (`f64`)
+    F64,
+}
+
+impl FloatWidth {
+    /// Number of bytes in a float of this width
+    pub fn bytes(self) -> usize {
+        match self {
+            FloatWidth::F32 => 4,
+            FloatWidth::F64 => 8,
+        }
+    }
+}
+
+/// Checksum of the bytes of each line, printed as a hex byte in a column of its own
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumKind {
+    /// All the bytes XORed together
+    Xor,
+    /// [CRC-8/SMBUS] of the bytes, with the polynomial `0x07`, e.g. `f4` for `123456789`
+    ///
+    /// [CRC-8/SMBUS]: https://reveng.sourceforge.io/crc-catalogue/1-15.htm#crc.cat.crc-8-smbus
+    Crc8,
+}
+
+impl ChecksumKind {
+    /// Compute the checksum of `bytes`
+    pub fn checksum(self, bytes: &[u8]) -> u8 {
+        match self {
+            ChecksumKind::Xor => bytes.iter().fold(0, |value, &byte| value ^ byte),
+            ChecksumKind::Crc8 => bytes.iter().fold(0, |mut value, &byte| {
+                value ^= byte;
+                for _ in 0..8 {
+                    value = match value & 0x80 {
+                        0 => value << 1,
+                        _ => (value << 1) ^ 0x07,
+                    };
+                }
+                value
+            }),
+        }
+    }
+
+    /// Label of the checksum column in the header, which is also the width of the column
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            ChecksumKind::Xor => "xor",
+            ChecksumKind::Crc8 => "crc8",
+        }
+    }
+}
+
+/// Positions and widths of the columns of a full line of the default layout, counted in characters from the start
+/// of the line, see [`ClassicRenderer::layout`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout {
+    /// Width of the offset column, which is 0 if it isn't shown
+    pub offset_width: usize,
+    /// Position of the first character of the data column
+    pub hex_column_start: usize,
+    /// Width of the data column
+    pub hex_column_width: usize,
+    /// Position of the first character of the text column, if it is shown
+    pub ascii_column_start: Option<usize>,
+    /// Width of a full line, not counting any annotation after the last column
+    pub total_width: usize,
+}
+
+/// Options of the default layout that decide how many bytes fit on a line, see [`max_line_width_for`]
+#[derive(Clone, Debug)]
+pub struct LayoutOptions {
+    offset_width: usize,
+    show_offset: bool,
+    show_ascii: bool,
+    byte_group_length: usize,
+    group_gap: Option<usize>,
+    radix: Radix,
+    text_mode: TextMode,
+    separators: SeparatorStyle,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        LayoutOptions {
+            offset_width: 8,
+            show_offset: true,
+            show_ascii: true,
+            byte_group_length: 1,
+            group_gap: None,
+            radix: Radix::Hex,
+            text_mode: TextMode::Characters,
+            separators: SeparatorStyle::classic(),
+        }
+    }
+}
+
+impl LayoutOptions {
+    /// Construct a new instance of [`LayoutOptions`] with the options of the default layout
+    pub fn new() -> LayoutOptions {
+        LayoutOptions::default()
+    }
+
+    /// Set the number of characters in the offset column (8 by default, as for offsets up to 32 bits in hex)
+    pub fn offset_width(mut self, offset_width: usize) -> LayoutOptions {
+        self.offset_width = offset_width;
+        self
+    }
+
+    /// Set whether the offset column is shown
+    pub fn show_offset(mut self, show_offset: bool) -> LayoutOptions {
+        self.show_offset = show_offset;
+        self
+    }
+
+    /// Set whether the text column is shown
+    pub fn show_ascii(mut self, show_ascii: bool) -> LayoutOptions {
+        self.show_ascii = show_ascii;
+        self
+    }
+
+    /// Set the number of bytes grouped together in the data column, see
+    /// [`Dumper::byte_group_length`](crate::Dumper::byte_group_length)
+    pub fn byte_group_length(mut self, byte_group_length: usize) -> LayoutOptions {
+        self.byte_group_length = byte_group_length.max(1);
+        self
+    }
+
+    /// Set the number of bytes after which an extra space goes between groups, see
+    /// [`Dumper::group_gap`](crate::Dumper::group_gap)
+    pub fn group_gap(mut self, every: Option<usize>) -> LayoutOptions {
+        self.group_gap = every.filter(|&every| every > 0);
+        self
+    }
+
+    /// Set the numeral system each byte is printed in
+    pub fn radix(mut self, radix: Radix) -> LayoutOptions {
+        self.radix = radix;
+        self
+    }
+
+    /// Set how bytes are represented in the text column
+    pub fn text_mode(mut self, text_mode: TextMode) -> LayoutOptions {
+        self.text_mode = text_mode;
+        self
+    }
+
+    /// Set the separators placed between columns
+    pub fn separators(mut self, separators: SeparatorStyle) -> LayoutOptions {
+        self.separators = separators;
+        self
+    }
+}
+
+/// The largest number of bytes per line whose lines fit in `total_columns` characters with the layout described by
+/// `opts`, as [`Dumper::line_width_auto`](crate::Dumper::line_width_auto) picks for the terminal
+///
+/// Multiples of the byte group length are preferred so that every group is full. The result is between 1 and 256,
+/// and is 1 if not even a single byte fits.
+pub fn max_line_width_for(total_columns: usize, opts: &LayoutOptions) -> usize {
+    let mut renderer = ClassicRenderer {
+        offset_width: opts.offset_width,
+        offset_digits: opts.offset_width,
+        show_offset: opts.show_offset,
+        show_ascii: opts.show_ascii,
+        byte_group_length: opts.byte_group_length,
+        group_gap: opts.group_gap,
+        radix: opts.radix,
+        text_mode: opts.text_mode,
+        separators: opts.separators.clone(),
+        ..ClassicRenderer::default()
+    };
+    renderer.fit_line_width(total_columns);
+    renderer.line_width
+}
+
+/// Text placed between the columns of the default layout, and the characters of the separator row below the header
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeparatorStyle {
+    /// Separator between the offset column and the data column
+    pub offset: String,
+    /// Separator between the data column and the text column, also used between any later columns
+    pub text: String,
+    /// Character filling the separator row below each column
+    pub fill: char,
+    /// Character of the separator row below every non-space character of a separator
+    pub corner: char,
+}
+
+impl SeparatorStyle {
+    /// The style used by default, e.g. `00000000 | 48 69 | Hi` with a `---------+-------+---` separator row
+    pub fn classic() -> SeparatorStyle {
+        SeparatorStyle {
+            offset: String::from(" | "),
+            text: String::from(" | "),
+            fill: '-',
+            corner: '+',
+        }
+    }
+
+    /// The part of the separator row below `separator`
+    pub(crate) fn row(&self, separator: &str) -> String {
+        separator
+            .chars()
+            .map(|c| if c == ' ' { self.fill } else { self.corner })
+            .collect()
+    }
+}
+
+impl Default for SeparatorStyle {
+    fn default() -> Self {
+        SeparatorStyle::classic()
+    }
+}
+
+/// Numeral system used to print the offset column
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OffsetBase {
+    /// Hexadecimal (base 16), e.g. `000000a0`
+    #[default]
+    Hex,
+    /// Decimal (base 10), e.g. `0000000160`
+    Decimal,
+    /// Octal (base 8), e.g. `00000000240`
+    Octal,
+}
+
+impl OffsetBase {
+    /// Number of digits the offset column needs to fit `max_offset`, widening from 32-bit to 64-bit offsets as needed
+    #[cfg(feature = "std")]
+    pub(crate) fn width(self, max_offset: u64) -> usize {
+        let wide = max_offset > u32::MAX as u64;
+        match (self, wide) {
+            (OffsetBase::Hex, false) => 8,
+            (OffsetBase::Hex, true) => 16,
+            (OffsetBase::Decimal, false) => 10,
+            (OffsetBase::Decimal, true) => 20,
+            (OffsetBase::Octal, false) => 11,
+            (OffsetBase::Octal, true) => 22,
+        }
+    }
+}
+
+/// Format `bytes` as a dump with `renderer`, returning the lines of its header, body and footer
+///
+/// Unlike [`Dumper`](crate::Dumper), this doesn't depend on `std::io`, so it is also available without the `std`
+/// feature. `line_width` should match the line width the renderer was configured with, if it has one.
+pub fn render_lines(
+    renderer: &mut dyn LineRenderer,
+    bytes: &[u8],
+    line_width: usize,
+    base_address: u64,
+) -> Vec<String> {
+    renderer.begin(Some(bytes.len() as u64));
+
+    let mut lines = Vec::new();
+    if let Some(header) = renderer.header() {
+        lines.extend(header.lines().map(String::from));
+    }
+
+    let line_count = bytes.len().div_ceil(line_width);
+    for (index, line_bytes) in bytes.chunks(line_width).enumerate() {
+        let offset = base_address + (index * line_width) as u64;
+        let mut line = renderer.render_line(offset, line_bytes);
+        if index + 1 < line_count {
+            line.push_str(renderer.line_delimiter());
+        }
+        lines.push(line);
+    }
+
+    if let Some(footer) = renderer.footer(bytes.len() as u64) {
+        lines.extend(footer.lines().map(String::from));
+    }
+    lines
+}
+
+/// Formats the lines of a dump, allowing the output layout of a [`Dumper`](crate::Dumper) to be customized
+pub trait LineRenderer {
+    /// Called before the header is formatted with the number of bytes that will be dumped, if it is known up front
+    ///
+    /// The length isn't known up front if the input length can't be measured, or if lines may be left out of the
+    /// dump (by [`Dumper::grep`](crate::Dumper::grep), for example).
+    fn begin(&mut self, _length: Option<u64>) {}
+
+    /// Format a single line of the dump, where `offset` is the offset of the first byte of `bytes`
+    fn render_line(&self, offset: u64, bytes: &[u8]) -> String;
+
+    /// Append a single line of the dump to `buf`, which renderers can implement to avoid allocating a string per
+    /// line
+    ///
+    /// `buf` isn't cleared first, so a caller reusing it for every line should clear it between calls.
+    fn render_line_into(&self, offset: u64, bytes: &[u8], buf: &mut String) {
+        buf.push_str(&self.render_line(offset, bytes));
+    }
+
+    /// Format the rows printed before the first line of the dump, if any
+    fn header(&self) -> Option<String> {
+        None
+    }
+
+    /// Format the rows printed after the last line of the dump, if any, given the total number of bytes dumped
+    fn footer(&self, _length: u64) -> Option<String> {
+        None
+    }
+
+    /// Text appended to every line of the dump except the last one
+    fn line_delimiter(&self) -> &str {
+        ""
+    }
+}
+
+/// ASCII hex digits of every byte value in lowercase, indexed by the byte
+const HEX_DIGITS_LOWER: [[u8; 2]; 256] = hex_digit_table(b"0123456789abcdef");
+
+/// ASCII hex digits of every byte value in uppercase, indexed by the byte
+const HEX_DIGITS_UPPER: [[u8; 2]; 256] = hex_digit_table(b"0123456789ABCDEF");
+
+const fn hex_digit_table(digits: &[u8; 16]) -> [[u8; 2]; 256] {
+    let mut table = [[0; 2]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        table[byte] = [digits[byte >> 4], digits[byte & 0xf]];
+        byte += 1;
+    }
+    table
+}
+
+/// Append the two hex digits of `byte` to `out`, which is much faster than formatting them with `write!`
+pub(crate) fn push_hex_byte(out: &mut String, byte: u8, uppercase: bool) {
+    let table = match uppercase {
+        true => &HEX_DIGITS_UPPER,
+        false => &HEX_DIGITS_LOWER,
+    };
+    let [high, low] = table[byte as usize];
+    out.push(high as char);
+    out.push(low as char);
+}
+
+/// Turn `name` into a valid C or Rust identifier by replacing any other characters with `_`
+pub(crate) fn identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !identifier.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
@@ -0,0 +1,35 @@
+use alloc::string::String;
+
+use super::{push_hex_byte, LineRenderer};
+
+/// A layout with only the bytes in hex and no offset or ASCII columns, like `xxd -p`
+///
+/// The dump can be turned back into the original bytes with [`undump_plain`](crate::undump_plain), as long as it
+/// wasn't squeezed.
+#[derive(Default)]
+pub struct PlainRenderer {
+    uppercase: bool,
+}
+
+impl PlainRenderer {
+    /// Construct a new instance of [`PlainRenderer`]
+    pub fn new() -> PlainRenderer {
+        PlainRenderer::default()
+    }
+
+    /// Set whether hex digits should be printed in uppercase
+    pub fn uppercase(mut self, uppercase: bool) -> PlainRenderer {
+        self.uppercase = uppercase;
+        self
+    }
+}
+
+impl LineRenderer for PlainRenderer {
+    fn render_line(&self, _offset: u64, bytes: &[u8]) -> String {
+        let mut line = String::with_capacity(2 * bytes.len());
+        for &byte in bytes {
+            push_hex_byte(&mut line, byte, self.uppercase);
+        }
+        line
+    }
+}
@@ -0,0 +1,69 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use super::{identifier, push_hex_byte, LineRenderer};
+
+/// A layout that assigns the bytes to a Python variable as a `bytes` literal, split into one literal per line which
+/// Python concatenates
+///
+/// Printable ASCII characters are shown as themselves, common control characters as `\n`, `\r` and `\t`, and every
+/// other byte as a `\x` escape.
+pub struct PythonBytesRenderer {
+    name: String,
+    uppercase: bool,
+}
+
+impl PythonBytesRenderer {
+    /// Construct a new instance of [`PythonBytesRenderer`] assigning to a variable called `name`
+    ///
+    /// Characters that aren't valid in a Python identifier are replaced with `_`.
+    pub fn new(name: &str) -> PythonBytesRenderer {
+        PythonBytesRenderer {
+            name: identifier(name),
+            uppercase: false,
+        }
+    }
+
+    /// Set whether the hex digits of `\x` escapes should be printed in uppercase
+    pub fn uppercase(mut self, uppercase: bool) -> PythonBytesRenderer {
+        self.uppercase = uppercase;
+        self
+    }
+}
+
+impl LineRenderer for PythonBytesRenderer {
+    fn render_line(&self, _offset: u64, bytes: &[u8]) -> String {
+        let mut line = String::with_capacity(7 + 4 * bytes.len());
+        line.push_str("    b'");
+        for &byte in bytes {
+            match byte {
+                b'\\' => line.push_str("\\\\"),
+                b'\'' => line.push_str("\\'"),
+                b'\n' => line.push_str("\\n"),
+                b'\r' => line.push_str("\\r"),
+                b'\t' => line.push_str("\\t"),
+                0x20..=0x7e => line.push(byte as char),
+                _ => {
+                    line.push_str("\\x");
+                    push_hex_byte(&mut line, byte, self.uppercase);
+                }
+            }
+        }
+        line.push('\'');
+        line
+    }
+
+    fn header(&self) -> Option<String> {
+        Some(format!("{} = (", self.name))
+    }
+
+    fn footer(&self, length: u64) -> Option<String> {
+        // without any literal between them, the parentheses would be an empty tuple
+        match length {
+            0 => Some("    b''\n)".to_string()),
+            _ => Some(")".to_string()),
+        }
+    }
+}
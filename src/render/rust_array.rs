@@ -0,0 +1,74 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use super::{identifier, push_hex_byte, LineRenderer};
+
+/// A layout that declares the bytes as a Rust constant, either a `[u8; N]` array or a `&[u8]` slice
+///
+/// A slice is always declared if the number of bytes dumped isn't known before they are, such as when the input
+/// has no known length or lines are left out of the dump.
+pub struct RustArrayRenderer {
+    name: String,
+    slice: bool,
+    uppercase: bool,
+    length: Option<u64>,
+}
+
+impl RustArrayRenderer {
+    /// Construct a new instance of [`RustArrayRenderer`] declaring a constant called `name`
+    ///
+    /// Characters that aren't valid in a Rust identifier are replaced with `_`.
+    pub fn new(name: &str) -> RustArrayRenderer {
+        RustArrayRenderer {
+            name: identifier(name),
+            slice: false,
+            uppercase: false,
+            length: None,
+        }
+    }
+
+    /// Set whether the constant should be declared as a `&[u8]` slice rather than a `[u8; N]` array
+    pub fn slice(mut self, slice: bool) -> RustArrayRenderer {
+        self.slice = slice;
+        self
+    }
+
+    /// Set whether hex digits should be printed in uppercase
+    pub fn uppercase(mut self, uppercase: bool) -> RustArrayRenderer {
+        self.uppercase = uppercase;
+        self
+    }
+}
+
+impl LineRenderer for RustArrayRenderer {
+    fn begin(&mut self, length: Option<u64>) {
+        self.length = length;
+    }
+
+    fn render_line(&self, _offset: u64, bytes: &[u8]) -> String {
+        let mut line = String::with_capacity(4 + 6 * bytes.len());
+        line.push_str("    ");
+        for (index, &byte) in bytes.iter().enumerate() {
+            if index > 0 {
+                line.push(' ');
+            }
+            line.push_str("0x");
+            push_hex_byte(&mut line, byte, self.uppercase);
+            line.push(',');
+        }
+        line
+    }
+
+    fn header(&self) -> Option<String> {
+        match (self.slice, self.length) {
+            (false, Some(length)) => Some(format!("const {}: [u8; {length}] = [", self.name)),
+            _ => Some(format!("const {}: &[u8] = &[", self.name)),
+        }
+    }
+
+    fn footer(&self, _length: u64) -> Option<String> {
+        Some("];".to_string())
+    }
+}
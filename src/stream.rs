@@ -0,0 +1,92 @@
+use std::io::{self, Write};
+
+use crate::{Dumper, WritingDumper};
+
+/// A hex dumper fed with chunks of bytes, writing each line of the dump as soon as all of its bytes have arrived
+///
+/// Unlike [`WritingDumper`], the output writer is passed to every call, so the dump can be written wherever the
+/// chunks are handled. The bytes of an incomplete line are kept until the next call to [`StreamDumper::feed`], and
+/// the offsets keep counting up across calls. As with [`WritingDumper`], only the layout options of the [`Dumper`] it
+/// was built from apply.
+pub struct StreamDumper {
+    /// Formats the dump into a buffer, which is emptied into the output writer after every call
+    dumper: Option<WritingDumper<Vec<u8>>>,
+}
+
+impl StreamDumper {
+    /// Construct a new instance of [`StreamDumper`] with the default options
+    pub fn new() -> StreamDumper {
+        Dumper::new(io::empty()).into_stream()
+    }
+
+    pub(crate) fn from_writer(dumper: WritingDumper<Vec<u8>>) -> StreamDumper {
+        StreamDumper {
+            dumper: Some(dumper),
+        }
+    }
+
+    /// Format `bytes` as the next bytes of the input, writing the lines they complete to `out`
+    ///
+    /// Returns an error if the dump has already been finished.
+    pub fn feed(&mut self, bytes: &[u8], out: &mut impl Write) -> io::Result<()> {
+        let Some(dumper) = self.dumper.as_mut() else {
+            return Err(io::Error::other("the dump has already been finished"));
+        };
+
+        dumper.write_all(bytes)?;
+        let output = dumper.output_mut();
+        out.write_all(output)?;
+        output.clear();
+        Ok(())
+    }
+
+    /// Write the bytes of an incomplete line along with the footer to `out`, ending the dump
+    ///
+    /// Calling it again once the dump has been finished does nothing.
+    pub fn finish(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let Some(dumper) = self.dumper.take() else {
+            return Ok(());
+        };
+
+        out.write_all(&dumper.finish()?)?;
+        out.flush()
+    }
+}
+
+impl Default for StreamDumper {
+    fn default() -> Self {
+        StreamDumper::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_dumper() {
+        let bytes = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+
+        let expected = Dumper::new(&bytes[..])
+            .line_width(8)
+            .base_address(0x100)
+            .render()
+            .unwrap()
+            + "\n";
+
+        let mut stream = Dumper::new(io::empty())
+            .line_width(8)
+            .base_address(0x100)
+            .into_stream();
+        let mut output = Vec::new();
+        stream.feed(&bytes[..5], &mut output).unwrap();
+        assert!(output.is_empty());
+        for chunk in bytes[5..].chunks(5) {
+            stream.feed(chunk, &mut output).unwrap();
+        }
+        stream.finish(&mut output).unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+        assert!(stream.feed(b"more", &mut Vec::new()).is_err());
+    }
+}
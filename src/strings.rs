@@ -0,0 +1,115 @@
+use std::io::{self, BufReader, Read, Write};
+
+use crate::TextEncoding;
+
+/// Options controlling the output of [`extract_strings`]
+#[derive(Clone, Debug)]
+pub struct StringsOptions {
+    min_length: usize,
+    include_null: bool,
+}
+
+impl Default for StringsOptions {
+    fn default() -> Self {
+        StringsOptions {
+            min_length: 4,
+            include_null: false,
+        }
+    }
+}
+
+impl StringsOptions {
+    /// Construct a new instance of [`StringsOptions`] with the default options
+    pub fn new() -> StringsOptions {
+        StringsOptions::default()
+    }
+
+    /// Set the minimum number of printable bytes in a row for them to be printed as a string (4 by default)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_length` is 0
+    pub fn min_length(mut self, min_length: usize) -> StringsOptions {
+        assert!(min_length > 0, "minimum string length must be at least 1");
+        self.min_length = min_length;
+        self
+    }
+
+    /// Set whether strings ended by a null byte should be printed with a trailing `\0`, which tells them apart from
+    /// the ones that run into other unprintable bytes
+    pub fn include_null(mut self, include_null: bool) -> StringsOptions {
+        self.include_null = include_null;
+        self
+    }
+}
+
+/// Whether `byte` is part of a string, which are made of the bytes shown as characters in the default ASCII
+/// column along with tabs
+fn is_printable(byte: u8) -> bool {
+    byte == b'\t' || TextEncoding::Ascii.decode(byte).is_some()
+}
+
+/// Write every run of printable bytes in `reader` that is long enough to `writer`, like `strings`, one per line as
+/// `OFFSET: string` with the hex offset of its first byte
+pub fn extract_strings<R: Read, W: Write>(
+    reader: R,
+    writer: &mut W,
+    opts: StringsOptions,
+) -> io::Result<()> {
+    let mut run = Vec::new();
+    let mut run_offset = 0;
+
+    let mut write_run = |run: &mut Vec<u8>, run_offset: u64, terminator: Option<u8>| {
+        let result = match run.len() >= opts.min_length {
+            true => {
+                let null = match (terminator, opts.include_null) {
+                    (Some(0), true) => "\\0",
+                    _ => "",
+                };
+                let string = String::from_utf8_lossy(run);
+                writeln!(writer, "{run_offset:08x}: {string}{null}")
+            }
+            false => Ok(()),
+        };
+        run.clear();
+        result
+    };
+
+    for (offset, byte) in (0u64..).zip(BufReader::new(reader).bytes()) {
+        let byte = byte?;
+        if is_printable(byte) {
+            if run.is_empty() {
+                run_offset = offset;
+            }
+            run.push(byte);
+        } else if !run.is_empty() {
+            write_run(&mut run, run_offset, Some(byte))?;
+        }
+    }
+    write_run(&mut run, run_offset, None)?;
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn printable_runs() {
+        let expected = "00000002: Hello, world\\0\n\
+                        00000012: \tTab\n\
+                        0000001c: last";
+
+        let bytes = b"\x7f\x01Hello, world\0ab\x01\tTab\xff\x00xyz\x02last";
+        let mut output = Vec::new();
+        extract_strings(
+            &bytes[..],
+            &mut output,
+            StringsOptions::new().include_null(true),
+        )
+        .unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap().trim_end());
+    }
+}
@@ -0,0 +1,649 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::iter;
+
+use crate::DumperError;
+
+/// Parse a hex dump in the format produced by [`Dumper`](crate::Dumper) and write the reconstructed bytes to `writer`
+///
+/// Each line is placed at the position given by its offset column, and the output starts at the lowest offset, so a
+/// dump of part of a file (such as one taken with [`Dumper::skip`](crate::Dumper::skip) or a base address) gives
+/// back just the dumped bytes. Lines may be reordered or have gaps of at most [`MAX_UNDUMP_GAP`] bytes between them
+/// (which are filled with zeros), as long as no line starts more than [`MAX_UNDUMP_GAP`] bytes before the end of the
+/// furthest line so far, since only those last bytes are kept in memory before being written. A `*` line of a
+/// squeezed dump stands for the previous line repeated up to the offset of the next line. The separator row and ascii column are ignored, while the header row
+/// gives the number of bytes per group, which every group of the hex column must then hold.
+///
+/// Only dumps with hex bytes in input order and an offset column made of a single hex number can be reverted, so a
+/// line that has bytes in another radix or an extra column (such as the line numbers) before the hex column is a
+/// parse error rather than being read as the wrong bytes. So are the layouts given away by the width of the offset
+/// column or the step between the offsets of two lines (decimal or octal offsets, and lines left out by
+/// [`Dumper::stride`](crate::Dumper::stride) or [`Dumper::grep`](crate::Dumper::grep), which can't be told apart from
+/// a gap of whole lines), by the header (reversed lines) or by the text column (little-endian groups).
+///
+/// If the first line that isn't blank or a separator row has no column separator, the input is taken to be bare hex
+/// instead and parsed like [`undump_plain`] does, as long as that line is made of hex words. A first word that is
+/// longer than the next one or made of at least four zeros looks like an offset column (as in
+/// [`Dumper::compact`](crate::Dumper::compact) dumps without an ascii column), which is a parse error rather than
+/// being read as bytes; [`undump_plain`] reads such input as bare hex regardless.
+pub fn undump<R: Read, W: Write>(reader: R, writer: &mut W) -> Result<(), DumperError> {
+    let mut output = Vec::new();
+    let mut plain: Option<PlainHex> = None;
+    let mut window = Window {
+        writer: &mut *writer,
+        start: None,
+        bytes: Vec::new(),
+        flushed: false,
+    };
+    // the offset just past the previous line, once the first line has been parsed
+    let mut position: Option<u64> = None;
+    // the number of hex digits of every group, if the header row gives it
+    let mut group_digits: Option<usize> = None;
+    // the offset column and bytes of the previous line, and whether a `*` line has said that they repeat
+    let mut previous_offset = String::new();
+    let mut previous_bytes = Vec::new();
+    // the length of the longest line so far, which is the length of every line but the last one
+    let mut line_length = 0;
+    let mut squeezed = false;
+    let mut line_number = 0;
+
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        line_number = index + 1;
+
+        if let Some(plain) = plain.as_mut() {
+            plain.parse_line(&line, line_number, &mut output)?;
+            window.writer.write_all(&output)?;
+            output.clear();
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if position.is_none()
+            && !trimmed.is_empty()
+            && !trimmed.starts_with('-')
+            && !line.contains('|')
+        {
+            if !is_bare_hex(&line) {
+                return Err(parse_error(
+                    line_number,
+                    "missing column separator, and the line isn't bare hex",
+                ));
+            }
+            plain
+                .insert(PlainHex::default())
+                .parse_line(&line, line_number, &mut output)?;
+            window.writer.write_all(&output)?;
+            output.clear();
+            continue;
+        }
+
+        if trimmed.starts_with('*') {
+            if position.is_none() {
+                return Err(parse_error(
+                    line_number,
+                    "squeeze marker before the first line",
+                ));
+            }
+            squeezed = true;
+            continue;
+        }
+
+        let mut columns = line.splitn(3, '|');
+        let offset_column = columns.next().unwrap_or_default();
+        let Some(hex_column) = columns.next() else {
+            if trimmed.is_empty() || trimmed.starts_with('-') {
+                continue;
+            }
+            return Err(parse_error(line_number, "missing column separator"));
+        };
+
+        // the byte offsets header row has an empty offset column, and its first two labels are a group apart
+        if offset_column.trim().is_empty() {
+            let labels: Vec<u64> = hex_column
+                .split_whitespace()
+                .map_while(|label| u64::from_str_radix(label, 16).ok())
+                .take(2)
+                .collect();
+            if let [first, second] = labels[..] {
+                if first > second {
+                    return Err(parse_error(line_number, "lines are reversed"));
+                }
+                group_digits = usize::try_from(second - first)
+                    .ok()
+                    .map(|length| 2 * length);
+            }
+            continue;
+        }
+
+        // the offset column is zero-padded, so a line starting with a space has another column before it
+        if offset_column.starts_with(char::is_whitespace) {
+            return Err(parse_error(
+                line_number,
+                "unexpected column before the offset",
+            ));
+        }
+        let offset_column = offset_column.trim_end();
+        // decimal and octal offsets are given away by the width their column has unless it was fixed
+        let is_decimal = offset_column.bytes().all(|digit| digit.is_ascii_digit());
+        if is_decimal && matches!(offset_column.len(), 10 | 20) {
+            return Err(parse_error(line_number, "offsets are decimal, not hex"));
+        }
+        let is_octal = offset_column
+            .bytes()
+            .all(|digit| matches!(digit, b'0'..=b'7'));
+        if is_octal && matches!(offset_column.len(), 11 | 22) {
+            return Err(parse_error(line_number, "offsets are octal, not hex"));
+        }
+        let offset = u64::from_str_radix(offset_column, 16)
+            .map_err(|_| parse_error(line_number, "invalid offset"))?;
+        let bytes = parse_hex_column(hex_column, group_digits)
+            .ok_or_else(|| parse_error(line_number, "malformed hex"))?;
+        // with line numbers, the offset looks like a hex column of a single group followed by the actual one
+        let text_column = columns.next().unwrap_or_default();
+        if hex_column.split_whitespace().count() == 1
+            && text_column.split_once('|').is_some_and(|(column, _)| {
+                parse_hex_column(column, None).is_some_and(|bytes| !bytes.is_empty())
+            })
+        {
+            return Err(parse_error(
+                line_number,
+                "unexpected column before the hex column",
+            ));
+        }
+        // the text column shows the bytes in input order, while the groups of the hex column may be swapped
+        let text = text_column.split('|').next().unwrap_or_default();
+        let text = text.strip_prefix(' ').unwrap_or(text);
+        let swapped_bytes: Vec<u8> = hex_column
+            .split_whitespace()
+            .flat_map(|group| {
+                parse_hex_column(group, None)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .rev()
+            })
+            .collect();
+        if text_mismatches(&bytes, text) > 0 && text_mismatches(&swapped_bytes, text) == 0 {
+            return Err(parse_error(line_number, "groups are little-endian"));
+        }
+
+        if position.is_some_and(|position| offset > position) && !squeezed {
+            let length = previous_bytes.len() as u64;
+            let message = unexpected_step(&previous_offset, offset_column, length);
+            if let Some(message) = message.filter(|_| previous_bytes.len() == line_length) {
+                return Err(parse_error(line_number, message));
+            }
+        }
+
+        if squeezed {
+            // the repeats continue the previous line, which must be the furthest one so far
+            if position != window.end() || position.is_some_and(|position| offset < position) {
+                return Err(parse_error(line_number, "squeezed lines must be in order"));
+            }
+            window.write_repeated(&previous_bytes, offset)?;
+        }
+        window.place(offset, &bytes, line_number)?;
+        position = Some(offset + bytes.len() as u64);
+        line_length = line_length.max(bytes.len());
+        previous_offset = offset_column.to_string();
+        previous_bytes = bytes;
+        squeezed = false;
+    }
+
+    if squeezed {
+        return Err(parse_error(
+            line_number,
+            "squeeze marker isn't followed by a line",
+        ));
+    }
+    if let Some(plain) = plain {
+        plain.finish(line_number)?;
+    }
+    window.write_behind(0)?;
+    Ok(())
+}
+
+/// Explain why a line that doesn't start right after the previous line, which was a whole line of `length` bytes,
+/// looks like part of a layout that can't be reverted, if it does, given the offset columns of both lines
+fn unexpected_step(previous_offset: &str, offset: &str, length: u64) -> Option<&'static str> {
+    let step = |radix| {
+        let previous_offset = u64::from_str_radix(previous_offset, radix).ok()?;
+        u64::from_str_radix(offset, radix)
+            .ok()?
+            .checked_sub(previous_offset)
+    };
+    let gap = step(16)?.checked_sub(length)?;
+    if length == 0 || gap == 0 {
+        return None;
+    }
+
+    if step(10) == Some(length) {
+        Some("offsets are decimal, not hex")
+    } else if gap.is_multiple_of(length) {
+        Some("lines are left out, as in a sampled or filtered dump")
+    } else if step(8) == Some(length) {
+        Some("offsets are octal, not hex")
+    } else {
+        None
+    }
+}
+
+/// Number of the printable bytes of `bytes` that the text column `text` shows as another printable character
+fn text_mismatches(bytes: &[u8], text: &str) -> usize {
+    bytes
+        .iter()
+        .zip(text.chars())
+        .filter(|&(&byte, c)| byte.is_ascii_graphic() && c.is_ascii_graphic() && c != byte as char)
+        .count()
+}
+
+/// Largest number of missing bytes between two lines that [`undump`] fills with zeros, so that a corrupt offset
+/// doesn't produce a huge output, which is also how far back a line can go
+pub const MAX_UNDUMP_GAP: u64 = 1 << 20;
+
+/// The last bytes reconstructed by [`undump`], which a line further on in the dump may still overwrite
+struct Window<'a, W: Write> {
+    writer: &'a mut W,
+    /// Offset of the first byte of `bytes`, once the first line has been parsed
+    start: Option<u64>,
+    bytes: Vec<u8>,
+    /// Whether bytes before `start` have been written, so that lines can no longer go before it
+    flushed: bool,
+}
+
+impl<W: Write> Window<'_, W> {
+    /// Offset just past the furthest byte reconstructed so far
+    fn end(&self) -> Option<u64> {
+        self.start.map(|start| start + self.bytes.len() as u64)
+    }
+
+    /// Put the `bytes` of a line at `offset`, filling the gap between them and the bytes around them with zeros
+    fn place(&mut self, offset: u64, bytes: &[u8], line_number: usize) -> Result<(), DumperError> {
+        let start = *self.start.get_or_insert(offset);
+        if offset < start {
+            if self.flushed || start - offset > MAX_UNDUMP_GAP {
+                return Err(parse_error(
+                    line_number,
+                    "offset is too far before the previous lines",
+                ));
+            }
+            self.bytes
+                .splice(0..0, iter::repeat_n(0, (start - offset) as usize));
+            self.start = Some(offset);
+        }
+
+        let index = offset - self.start.unwrap_or(offset);
+        if index > self.bytes.len() as u64 + MAX_UNDUMP_GAP {
+            return Err(parse_error(line_number, "gap before the line is too large"));
+        }
+        let index = index as usize;
+        let end = index + bytes.len();
+        if self.bytes.len() < end {
+            self.bytes.resize(end, 0);
+        }
+        self.bytes[index..end].copy_from_slice(bytes);
+
+        self.write_behind(MAX_UNDUMP_GAP as usize)?;
+        Ok(())
+    }
+
+    /// Write the bytes up to `offset` made of `pattern` repeated over and over, after every byte reconstructed so
+    /// far
+    fn write_repeated(&mut self, pattern: &[u8], offset: u64) -> Result<(), DumperError> {
+        self.write_behind(0)?;
+        let mut remaining = offset - self.end().unwrap_or(offset);
+        // the pattern is written many times at once, and a chunk always ends at the end of a repeat
+        let repeats = (0x1000 / pattern.len().max(1)).max(1);
+        let chunk = pattern.repeat(repeats);
+        while remaining > 0 {
+            let length = remaining.min(chunk.len() as u64) as usize;
+            self.writer.write_all(&chunk[..length])?;
+            remaining -= length as u64;
+        }
+        self.start = Some(offset);
+        self.flushed = true;
+        Ok(())
+    }
+
+    /// Write out every byte but the last `keep` ones
+    fn write_behind(&mut self, keep: usize) -> Result<(), DumperError> {
+        let Some(length) = self
+            .bytes
+            .len()
+            .checked_sub(keep)
+            .filter(|&length| length > 0)
+        else {
+            return Ok(());
+        };
+        self.writer.write_all(&self.bytes[..length])?;
+        self.bytes.drain(..length);
+        self.start = self.start.map(|start| start + length as u64);
+        self.flushed = true;
+        Ok(())
+    }
+}
+
+/// Parse bare hex, such as a hex dump in the format produced by [`PlainRenderer`](crate::PlainRenderer), and write
+/// the reconstructed bytes to `writer`, which is the inverse of dumping with it
+///
+/// The input is a stream of hex digits in either case, each pair of which is a byte, so it can be pasted from
+/// anywhere, e.g. `4c6f7265` or `0x4c 0x6f 72 65`. Whitespace (including line breaks, so a byte may be split across
+/// lines) and a `0x` prefix on each whitespace-separated word are ignored. Squeezed lines can't be reverted, since
+/// the bytes they stand for aren't known.
+pub fn undump_plain<R: Read, W: Write>(reader: R, writer: &mut W) -> Result<(), DumperError> {
+    let mut plain = PlainHex::default();
+    let mut bytes = Vec::new();
+    let mut line_number = 0;
+
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        line_number = index + 1;
+
+        plain.parse_line(&line, line_number, &mut bytes)?;
+        writer.write_all(&bytes)?;
+        bytes.clear();
+    }
+
+    plain.finish(line_number)
+}
+
+/// Strip the `0x` prefix of a word of bare hex, if it has one
+fn strip_hex_prefix(word: &str) -> &str {
+    word.strip_prefix("0x")
+        .or_else(|| word.strip_prefix("0X"))
+        .unwrap_or(word)
+}
+
+/// Whether `line` looks like bare hex rather than a line of a dump without column separators, which starts with an
+/// offset
+fn is_bare_hex(line: &str) -> bool {
+    let words: Vec<&str> = line.split_whitespace().map(strip_hex_prefix).collect();
+    let is_hex = words
+        .iter()
+        .all(|word| word.chars().all(|c| c.is_ascii_hexdigit()));
+    let starts_with_offset = match words[..] {
+        [first, second, ..] => {
+            first.len() > second.len() || (first.len() >= 4 && first.chars().all(|c| c == '0'))
+        }
+        _ => false,
+    };
+    is_hex && !starts_with_offset
+}
+
+/// Parser of bare hex, which carries the first digit of a byte split across lines over to the next line
+#[derive(Default)]
+struct PlainHex {
+    high_digit: Option<u8>,
+}
+
+impl PlainHex {
+    /// Parse the digits of `line` and append the bytes they complete to `bytes`
+    fn parse_line(
+        &mut self,
+        line: &str,
+        line_number: usize,
+        bytes: &mut Vec<u8>,
+    ) -> Result<(), DumperError> {
+        if line.trim() == "*" {
+            return Err(parse_error(line_number, "squeezed lines can't be reverted"));
+        }
+
+        for word in line.split_whitespace() {
+            for c in strip_hex_prefix(word).chars() {
+                let digit = c
+                    .to_digit(16)
+                    .ok_or_else(|| parse_error(line_number, "malformed hex"))?
+                    as u8;
+                match self.high_digit.take() {
+                    Some(high_digit) => bytes.push(high_digit << 4 | digit),
+                    None => self.high_digit = Some(digit),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the input didn't end in the middle of a byte, where `line_number` is the number of its last line
+    fn finish(self, line_number: usize) -> Result<(), DumperError> {
+        match self.high_digit {
+            Some(_) => Err(parse_error(line_number, "odd number of hex digits")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse the groups of hex digits in a hex column, each of which must be whole bytes, and `group_digits` long if
+/// given except for the last group of a line, which may be shorter
+fn parse_hex_column(hex: &str, group_digits: Option<usize>) -> Option<Vec<u8>> {
+    let groups: Vec<&str> = hex.split_whitespace().collect();
+    let mut bytes = Vec::new();
+    for (index, group) in groups.iter().enumerate() {
+        let is_last = index == groups.len() - 1;
+        let length_matches = group_digits.is_none_or(|digits| match is_last {
+            true => group.len() <= digits,
+            false => group.len() == digits,
+        });
+        if !group.is_ascii() || group.len() % 2 != 0 || !length_matches {
+            return None;
+        }
+
+        for pair in group.as_bytes().chunks(2) {
+            let pair = std::str::from_utf8(pair).ok()?;
+            bytes.push(u8::from_str_radix(pair, 16).ok()?);
+        }
+    }
+    Some(bytes)
+}
+
+fn parse_error(line: usize, message: &str) -> DumperError {
+    DumperError::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{Dumper, Endian, OffsetBase, PlainRenderer, Radix};
+
+    #[test]
+    fn round_trip() {
+        let lorem = b"Lorem ipsum dolor sit amet | consectetur adipisicing elit.".to_vec();
+        let dump = Dumper::new(Cursor::new(lorem.clone()))
+            .byte_group_length(2)
+            .render()
+            .unwrap();
+
+        let mut result = Vec::new();
+        undump(dump.as_bytes(), &mut result).unwrap();
+
+        assert_eq!(lorem, result);
+    }
+
+    #[test]
+    fn reordered_lines() {
+        let dump = "00000004 | 6f 21 | o!\n\
+                    00000000 | 48 65 6c 6c | Hell";
+
+        let mut result = Vec::new();
+        undump(dump.as_bytes(), &mut result).unwrap();
+
+        assert_eq!(b"Hello!".to_vec(), result);
+    }
+
+    #[test]
+    fn gaps_between_lines() {
+        let dump = "7f3a1c200000 | 48 65 | He\n\
+                    7f3a1c200005 | 6c 6f | lo";
+
+        let mut result = Vec::new();
+        undump(dump.as_bytes(), &mut result).unwrap();
+        assert_eq!(b"He\0\0\0lo".to_vec(), result);
+
+        let dump = "00000000 | 48 65 | He\n\
+                    7f3a1c200000 | 6c 6f | lo";
+        let result = undump(dump.as_bytes(), &mut Vec::new());
+        assert!(matches!(result, Err(DumperError::Parse { line: 2, .. })));
+
+        let dump = "7f3a1c200000 | 48 65 | He\n\
+                    00000000 | 6c 6f | lo";
+        let result = undump(dump.as_bytes(), &mut Vec::new());
+        assert!(matches!(result, Err(DumperError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn lines_behind_the_window() {
+        // the first line has been written out by the time the last one goes back to it
+        let mut bytes = vec![0x41; MAX_UNDUMP_GAP as usize];
+        bytes.extend(b"Hello");
+        let mut dump = Dumper::new(Cursor::new(bytes)).render().unwrap();
+        dump.push_str("\n00000000 | 42 | B");
+
+        let result = undump(dump.as_bytes(), &mut Vec::new());
+        assert!(matches!(result, Err(DumperError::Parse { .. })));
+    }
+
+    #[test]
+    fn plain_round_trip() {
+        // a xorshift generator, so that the same inputs are tested on every run
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..500 {
+            let length = (next() % 200) as usize;
+            let bytes: Vec<u8> = (0..length).map(|_| next() as u8).collect();
+            let line_width = (next() % 32 + 1) as usize;
+            let uppercase = next() % 2 == 0;
+
+            let mut dump = Vec::new();
+            Dumper::new(Cursor::new(bytes.clone()))
+                .line_width(line_width)
+                .with_renderer(Box::new(PlainRenderer::new().uppercase(uppercase)))
+                .dump_to(&mut dump)
+                .unwrap();
+
+            let mut result = Vec::new();
+            undump_plain(&dump[..], &mut result).unwrap();
+
+            assert_eq!(bytes, result, "line width {line_width}");
+        }
+    }
+
+    #[test]
+    fn bare_hex() {
+        let expected = b"Lorem ipsum".to_vec();
+
+        for hex in [
+            "4c6f72656d20697073756d",
+            "4c6f 7265 6d20\n6970 7375 6d\n",
+            "0x4c 0x6F 0x72 0x65 0x6d 20 69\r\n70 73 75 6d",
+            "4c6f72656d2\n0697073756d",
+        ] {
+            let mut result = Vec::new();
+            undump(hex.as_bytes(), &mut result).unwrap();
+            assert_eq!(expected, result, "{hex:?}");
+
+            let mut result = Vec::new();
+            undump_plain(hex.as_bytes(), &mut result).unwrap();
+            assert_eq!(expected, result, "{hex:?}");
+        }
+
+        let result = undump_plain("4c6f7".as_bytes(), &mut Vec::new());
+        assert!(matches!(result, Err(DumperError::Parse { line: 1, .. })));
+    }
+
+    #[test]
+    fn squeezed_round_trip() {
+        let mut bytes = b"Lorem ipsum".to_vec();
+        bytes.extend([0; 0x30]);
+        bytes.extend(b"dolor".repeat(20));
+        bytes.extend([0; 0x25]);
+
+        for squeeze_count in [false, true] {
+            let dump = Dumper::new(Cursor::new(bytes.clone()))
+                .line_width(5)
+                .squeeze(true)
+                .squeeze_count(squeeze_count)
+                .render()
+                .unwrap();
+            assert!(dump.contains('*'));
+
+            let mut result = Vec::new();
+            undump(dump.as_bytes(), &mut result).unwrap();
+            assert_eq!(bytes, result);
+        }
+    }
+
+    #[test]
+    fn compact_dump_is_not_bare_hex() {
+        let bytes = b"@A@A@A@A@A@A@A@A@A@A".to_vec();
+        for byte_group_length in [1, 4] {
+            let dump = Dumper::new(Cursor::new(bytes.clone()))
+                .compact(true)
+                .show_ascii(false)
+                .byte_group_length(byte_group_length)
+                .render()
+                .unwrap();
+
+            let result = undump(dump.as_bytes(), &mut Vec::new());
+            assert!(
+                matches!(result, Err(DumperError::Parse { line: 1, .. })),
+                "{dump}"
+            );
+        }
+    }
+
+    #[test]
+    fn other_layouts() {
+        let bytes = b"@A@A@A@A@A@A@A@A@A@A".to_vec();
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit, sed do".to_vec();
+        let dumps = [
+            Dumper::new(Cursor::new(bytes.clone())).offset_base(OffsetBase::Decimal),
+            Dumper::new(Cursor::new(bytes.clone()))
+                .offset_base(OffsetBase::Decimal)
+                .offset_width(Some(8)),
+            Dumper::new(Cursor::new(bytes.clone())).offset_base(OffsetBase::Octal),
+            Dumper::new(Cursor::new(bytes.clone()))
+                .endian(Endian::Little)
+                .byte_group_length(4),
+            Dumper::new(Cursor::new(bytes.clone())).reverse_line(true),
+            Dumper::new(Cursor::new(lorem.clone())).stride(2),
+            Dumper::new(Cursor::new(lorem.clone()))
+                .stride(2)
+                .header(false),
+            Dumper::new(Cursor::new(lorem.clone())).grep(b"o".to_vec()),
+            Dumper::new(Cursor::new(bytes.clone())).radix(Radix::Decimal),
+            Dumper::new(Cursor::new(bytes.clone()))
+                .radix(Radix::Decimal)
+                .byte_group_length(2),
+            Dumper::new(Cursor::new(bytes.clone())).radix(Radix::Binary),
+            Dumper::new(Cursor::new(bytes.clone())).line_numbers(true),
+            Dumper::new(Cursor::new(bytes[..4].to_vec()))
+                .line_numbers(true)
+                .header(false),
+        ];
+
+        for mut dumper in dumps {
+            let dump = dumper.render().unwrap();
+            let result = undump(dump.as_bytes(), &mut Vec::new());
+            assert!(matches!(result, Err(DumperError::Parse { .. })), "{dump}");
+        }
+    }
+
+    #[test]
+    fn malformed_hex() {
+        let dump = "00000000 | 48 65 6c 6c | Hell\n\
+                    00000004 | 6f 2 | o";
+
+        let result = undump(dump.as_bytes(), &mut Vec::new());
+
+        assert!(matches!(result, Err(DumperError::Parse { line: 2, .. })));
+    }
+}
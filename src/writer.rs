@@ -0,0 +1,213 @@
+use std::io::{self, Write};
+
+use crate::{dumper::check_offset_fits, ClassicRenderer, Dumper, LineRenderer};
+
+/// A sink that formats the bytes written to it as a hex dump and writes the dump to an output writer
+///
+/// Complete lines are written as soon as enough bytes have arrived, and [`Write::flush`] also writes the bytes of an
+/// incomplete line. Since the end of the input can't be detected, [`WritingDumper::finish`] must be called to end
+/// the dump with the footer of the renderer.
+///
+/// Only the layout options of the [`Dumper`] it was built from apply, not the options choosing which bytes are
+/// dumped (such as [`Dumper::skip`] or [`Dumper::squeeze`]). [`ColorChoice::Auto`](crate::ColorChoice::Auto) never
+/// colors the output, since `output` isn't known to be a terminal.
+pub struct WritingDumper<W>
+where
+    W: Write,
+{
+    output: W,
+    classic: ClassicRenderer,
+    renderer: Option<Box<dyn LineRenderer>>,
+    line_width: usize,
+    offset: u64,
+    bytes_written: u64,
+    /// Bytes of the line that is currently being filled
+    buffer: Vec<u8>,
+    /// The formatted line being written, reused for every line
+    line: String,
+    started: bool,
+    lines_written: usize,
+}
+
+impl<W> WritingDumper<W>
+where
+    W: Write,
+{
+    /// Construct a new instance of [`WritingDumper`] with the default options, writing the dump to `output`
+    pub fn new(output: W) -> WritingDumper<W> {
+        Dumper::new(io::empty()).into_writer(output)
+    }
+
+    pub(crate) fn from_parts(
+        output: W,
+        mut classic: ClassicRenderer,
+        mut renderer: Option<Box<dyn LineRenderer>>,
+        line_width: usize,
+        offset: u64,
+    ) -> WritingDumper<W> {
+        classic.fit_offset_width(offset, offset);
+        if let Some(renderer) = renderer.as_mut() {
+            renderer.begin(None);
+        }
+
+        WritingDumper {
+            output,
+            classic,
+            renderer,
+            line_width,
+            offset,
+            bytes_written: 0,
+            buffer: Vec::new(),
+            line: String::new(),
+            started: false,
+            lines_written: 0,
+        }
+    }
+
+    /// The output writer, which the dump written so far can be taken out of
+    pub(crate) fn output_mut(&mut self) -> &mut W {
+        &mut self.output
+    }
+
+    fn renderer(&self) -> &dyn LineRenderer {
+        self.renderer.as_deref().unwrap_or(&self.classic)
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.started {
+            return Ok(());
+        }
+        self.started = true;
+
+        if let Some(header) = self.renderer().header() {
+            for line in header.lines() {
+                writeln!(self.output, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Format and write the bytes in the buffer as a line of the dump
+    fn write_buffered_line(&mut self) -> io::Result<()> {
+        self.write_header()?;
+        check_offset_fits(&self.classic, self.offset)?;
+
+        // the delimiter only goes between lines, so if there is one it is written before each line but the first,
+        // and the newline ending a line is only written once the next line or the footer comes
+        self.classic.line_number = self.lines_written as u64 + 1;
+        let renderer = self.renderer.as_deref().unwrap_or(&self.classic);
+        let delimiter = renderer.line_delimiter();
+        if self.lines_written > 0 && !delimiter.is_empty() {
+            writeln!(self.output, "{delimiter}")?;
+        }
+        self.line.clear();
+        renderer.render_line_into(self.offset, &self.buffer, &mut self.line);
+        if delimiter.is_empty() {
+            self.line.push('\n');
+        }
+        self.output.write_all(self.line.as_bytes())?;
+
+        self.lines_written += 1;
+        self.offset += self.buffer.len() as u64;
+        self.bytes_written += self.buffer.len() as u64;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Write the bytes of an incomplete line along with the footer, and return the output writer
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.buffer.is_empty() {
+            self.write_buffered_line()?;
+        }
+        self.write_header()?;
+        if self.lines_written > 0 && !self.renderer().line_delimiter().is_empty() {
+            writeln!(self.output)?;
+        }
+
+        if let Some(footer) = self.renderer().footer(self.bytes_written) {
+            for line in footer.lines() {
+                writeln!(self.output, "{line}")?;
+            }
+        }
+
+        self.output.flush()?;
+        Ok(self.output)
+    }
+}
+
+impl<W> Write for WritingDumper<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            self.buffer.push(byte);
+            if self.buffer.len() == self.line_width {
+                self.write_buffered_line()?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.write_buffered_line()?;
+        }
+        self.output.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CIncludeRenderer;
+
+    #[test]
+    fn matches_dumper() {
+        let bytes = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+
+        let expected = Dumper::new(&bytes[..]).render().unwrap() + "\n";
+
+        let mut writer = WritingDumper::new(Vec::new());
+        for chunk in bytes.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        let output = writer.finish().unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn line_written_with_newline() {
+        let mut writer = Dumper::new(io::empty())
+            .line_width(4)
+            .header(false)
+            .into_writer(Vec::new());
+        writer.write_all(b"Hello").unwrap();
+
+        assert_eq!(
+            "00000000 | 48 65 6c 6c | Hell\n",
+            String::from_utf8_lossy(writer.output_mut())
+        );
+    }
+
+    #[test]
+    fn flush_partial_line() {
+        let expected = "unsigned char HELLO[] = {\n  \
+                        0x48, 0x65, 0x6c,\n  \
+                        0x6c, 0x6f\n\
+                        };\n\
+                        unsigned int HELLO_len = 5;\n";
+
+        let mut writer = Dumper::new(io::empty())
+            .line_width(4)
+            .with_renderer(Box::new(CIncludeRenderer::new("HELLO")))
+            .into_writer(Vec::new());
+        writer.write_all(b"Hel").unwrap();
+        writer.flush().unwrap();
+        writer.write_all(b"lo").unwrap();
+        let output = writer.finish().unwrap();
+
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+}
@@ -0,0 +1,82 @@
+//! Dumping a region of a large address space through a seekable reader, like `/proc/<pid>/mem`
+
+#![cfg(feature = "std")]
+
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+use rxd::Dumper;
+
+/// Address the mapped region starts at, well past the 32-bit range
+const REGION_ADDRESS: u64 = 0x7f3a_1c20_0000;
+
+/// A reader over an address space where only one region is mapped, like the memory file of a process: reading
+/// anywhere else fails, and the end of the file can't be seeked to
+struct ProcessMemory {
+    region: Vec<u8>,
+    position: u64,
+}
+
+impl Read for ProcessMemory {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(index) = self.position.checked_sub(REGION_ADDRESS) else {
+            return Err(io::Error::other("address isn't mapped"));
+        };
+        let mut region = self.region.get(index as usize..).unwrap_or_default();
+        let length = region.read(buf)?;
+        self.position += length as u64;
+        Ok(length)
+    }
+}
+
+impl Seek for ProcessMemory {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = match pos {
+            SeekFrom::Start(position) => position,
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset).unwrap(),
+            SeekFrom::End(_) => return Err(io::Error::from(io::ErrorKind::InvalidInput)),
+        };
+        Ok(self.position)
+    }
+}
+
+#[test]
+fn skip_seeks_to_high_address() {
+    let expected = "                 | 00 01 02 03 04 05 06 07 |         \n\
+                    -----------------+-------------------------+---------\n\
+                    00007f3a1c200010 | 10 11 12 13 14 15 16 17 | ........\n\
+                    00007f3a1c200018 | 18 19 1a 1b             | ....";
+
+    let memory = ProcessMemory {
+        region: (0..0x40).collect(),
+        position: 0,
+    };
+    let result = Dumper::from_seekable(memory)
+        .line_width(8)
+        .skip(REGION_ADDRESS + 0x10)
+        .byte_count(Some(12))
+        .render()
+        .unwrap();
+
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn base_address_with_positioned_cursor() {
+    let expected = "                 | 00 01 02 03 04 05 06 07 |         \n\
+                    -----------------+-------------------------+---------\n\
+                    00007f3a1c200030 | 30 31 32 33 34 35 36 37 | 01234567\n\
+                    00007f3a1c200038 | 38 39                   | 89";
+
+    // the cursor starts inside the region, at the byte mapped to the base address
+    let mut cursor = Cursor::new((0..0x40).collect::<Vec<u8>>());
+    cursor.set_position(0x20);
+    let result = Dumper::from_seekable(cursor)
+        .line_width(8)
+        .base_address(REGION_ADDRESS + 0x20)
+        .skip(0x10)
+        .byte_count(Some(10))
+        .render()
+        .unwrap();
+
+    assert_eq!(expected, result);
+}